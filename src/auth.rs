@@ -0,0 +1,100 @@
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+use serde_derive::{Deserialize, Serialize};
+use crate::error::Error;
+use crate::paths;
+
+/// How the client authenticates against the iDRAC.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuthMode {
+    /// Log in once through the SessionService and reuse the X-Auth-Token.
+    #[default]
+    Session,
+    /// Send the user and password with every request.
+    Basic,
+}
+
+/// Seconds before the real expiry at which a cached session is no longer trusted.
+const EXPIRY_MARGIN: u64 = 30;
+
+/// A Redfish session created through `/redfish/v1/SessionService/Sessions`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Session {
+    pub token: String,
+    /// Resource path of the session, needed to delete it again.
+    pub location: String,
+    /// Idle timeout of the session in seconds, as reported by the SessionService.
+    pub timeout: u64,
+    /// Unix timestamp after which the iDRAC will have dropped the session.
+    pub expires_at: u64,
+    /// Whether the session was read from the on-disk cache rather than freshly created.
+    #[serde(skip)]
+    pub cached: bool,
+}
+
+impl Session {
+    pub fn new(token: String, location: String, timeout: u64) -> Session {
+        Session { token, location, timeout, expires_at: now() + timeout, cached: false }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        now() + EXPIRY_MARGIN >= self.expires_at
+    }
+
+    /// Pushes the expiry forward after the session has been used.
+    pub fn touch(&mut self) {
+        self.expires_at = now() + self.timeout;
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+fn cache_file(host: &str, user: &str) -> Option<PathBuf> {
+    let name = paths::file_name_for(&format!("{}@{}", user, host));
+    Some(paths::cache_dir()?.join("sessions").join(format!("{}.json", name)))
+}
+
+/// Loads a still valid session for `user` on `host` from the cache.
+pub fn load(host: &str, user: &str) -> Option<Session> {
+    let content = fs::read(cache_file(host, user)?).ok()?;
+    let mut session: Session = serde_json::from_slice(&content).ok()?;
+    if session.is_expired() {
+        return None;
+    }
+    session.cached = true;
+    Some(session)
+}
+
+/// Stores a session in the cache, readable by the current user only.
+pub fn store(host: &str, user: &str, session: &Session) -> Result<(), Error> {
+    let path = match cache_file(host, user) {
+        Some(path) => path,
+        None => return Ok(()),
+    };
+    let io_error = |source| Error::Io { context: format!("could not write session cache {}", path.display()), source };
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(io_error)?;
+    }
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+    let content = serde_json::to_vec(session).expect("session is always serializable");
+    options.open(&path)
+        .and_then(|mut file| file.write_all(&content))
+        .map_err(io_error)
+}
+
+/// Removes a cached session, if there is one.
+pub fn remove(host: &str, user: &str) {
+    if let Some(path) = cache_file(host, user) {
+        let _ = fs::remove_file(path);
+    }
+}
@@ -0,0 +1,5 @@
+pub mod diff_bios_attributes;
+pub mod get_bios_attribute;
+pub mod list_bios_attributes;
+pub mod registry;
+pub mod set_bios_attributes;
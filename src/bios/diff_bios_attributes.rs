@@ -0,0 +1,158 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use crate::error::Error;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use crate::bios::list_bios_attributes::BiosState;
+use crate::client::RedfishClient;
+use crate::output::{table, value_to_string, Render};
+
+/// BIOS attribute values to compare a host with, read from a file or from another host.
+#[derive(Debug)]
+pub struct Baseline {
+    /// Where the values came from, a file name or a profile, for messages.
+    pub source: String,
+    pub attributes: Map<String, Value>,
+}
+
+impl Baseline {
+    /// Reads a JSON or YAML baseline. Accepts a map of attribute names to values, a saved Bios
+    /// resource with an `Attributes` object, or the output of `bios list -o json` or `-o yaml`.
+    pub fn read(path: &Path) -> Result<Baseline, Error> {
+        let text = fs::read_to_string(path)
+            .map_err(|source| Error::Io { context: format!("could not read baseline {}", path.display()), source })?;
+        let invalid = |reason: String| Error::Usage(format!("invalid baseline {}: {}", path.display(), reason));
+
+        // YAML is a superset of JSON, so one parser covers both.
+        let value: Value = serde_yaml::from_str(&text).map_err(|e| invalid(e.to_string()))?;
+        let attributes = match value {
+            Value::Object(mut map) => match map.remove("Attributes") {
+                Some(Value::Object(attributes)) => attributes,
+                Some(_) => return Err(invalid(String::from("Attributes is not a map"))),
+                None => map,
+            },
+            Value::Array(items) => items.into_iter().map(|item| {
+                match (item.get("Name").and_then(Value::as_str), item.get("CurrentValue")) {
+                    (Some(name), Some(value)) => Ok((name.to_owned(), value.to_owned())),
+                    _ => Err(invalid(String::from("expected Name and CurrentValue in every list entry"))),
+                }
+            }).collect::<Result<_, _>>()?,
+            _ => return Err(invalid(String::from("expected a map of attributes or a list from `bios list`"))),
+        };
+        Ok(Baseline { source: path.display().to_string(), attributes })
+    }
+
+    /// Takes the current BIOS values of the host `client` talks to, named `source` in messages.
+    pub async fn from_host(source: &str, client: &RedfishClient) -> Result<Baseline, Error> {
+        let state = BiosState::load(client).await?;
+        Ok(Baseline { source: source.to_owned(), attributes: state.resource.attributes })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Change {
+    /// Only the compared host has the attribute.
+    Added,
+    /// Only the baseline has the attribute.
+    Removed,
+    Changed,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Change::Added => "Added",
+            Change::Removed => "Removed",
+            Change::Changed => "Changed",
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct BiosDifference {
+    #[serde(rename="Name")]
+    pub name: String,
+    #[serde(rename="Change")]
+    pub change: Change,
+    #[serde(rename="BaselineValue")]
+    pub baseline_value: Option<Value>,
+    #[serde(rename="CurrentValue")]
+    pub current_value: Option<Value>,
+}
+
+/// Attributes whose current value differs from the baseline.
+#[derive(Debug, Serialize)]
+pub struct BiosDiff {
+    #[serde(rename="Baseline")]
+    pub baseline: String,
+    #[serde(rename="Differences")]
+    pub differences: Vec<BiosDifference>,
+}
+
+impl BiosDiff {
+    /// Fails with a drift error when there are differences, for compliance checks.
+    pub fn check(&self) -> Result<(), Error> {
+        match self.differences.len() {
+            0 => Ok(()),
+            count => Err(Error::Drift(format!("{} BIOS attribute(s) differ from {}", count, self.baseline))),
+        }
+    }
+}
+
+impl Render for BiosDiff {
+    fn columns(&self) -> Vec<&'static str> {
+        vec!["Name", "Change", "BaselineValue", "CurrentValue"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        let value = |value: &Option<Value>| value.as_ref().map(value_to_string).unwrap_or_default();
+        self.differences.iter().map(|difference| vec![
+            difference.name.to_owned(),
+            difference.change.to_string(),
+            value(&difference.baseline_value),
+            value(&difference.current_value),
+        ]).collect()
+    }
+
+    fn human(&self) -> String {
+        if self.differences.is_empty() {
+            return format!("No differences from {}\n", self.baseline);
+        }
+        table(&["Name", "Change", "Baseline", "Current"], &self.rows())
+    }
+}
+
+/// Compares the current BIOS values of the selected system with `baseline`.
+///
+/// Pending values are not taken into account. Attributes the registry marks read-only, such as
+/// the service tag, and those named in `ignore` are left out, since they differ between hosts
+/// without being settings.
+pub async fn diff_bios_attributes(baseline: &Baseline, ignore: &[String], client: &RedfishClient) -> Result<BiosDiff, Error> {
+    let state = BiosState::load(client).await?;
+    let current = &state.resource.attributes;
+    let compared = |name: &String| {
+        !ignore.contains(name) && !state.registry.attribute(name).map(|a| a.read_only).unwrap_or(false)
+    };
+
+    let mut names: Vec<&String> = current.keys().chain(baseline.attributes.keys()).filter(|name| compared(name)).collect();
+    names.sort();
+    names.dedup();
+
+    let differences = names.into_iter().filter_map(|name| {
+        let change = match (baseline.attributes.get(name), current.get(name)) {
+            (None, Some(_)) => Change::Added,
+            (Some(_), None) => Change::Removed,
+            (Some(expected), Some(actual)) if expected != actual => Change::Changed,
+            _ => return None,
+        };
+        Some(BiosDifference {
+            name: name.to_owned(),
+            change,
+            baseline_value: baseline.attributes.get(name).cloned(),
+            current_value: current.get(name).cloned(),
+        })
+    }).collect();
+
+    Ok(BiosDiff { baseline: baseline.source.to_owned(), differences })
+}
@@ -0,0 +1,10 @@
+use crate::error::Error;
+use crate::bios::list_bios_attributes::{BiosAttribute, BiosState};
+use crate::client::RedfishClient;
+
+pub async fn get_bios_attribute(name: &str, client: &RedfishClient) -> Result<BiosAttribute, Error> {
+    BiosState::load(client)
+        .await?
+        .attribute(name)
+        .ok_or_else(|| Error::NotFound(format!("BIOS attribute '{}'", name)))
+}
@@ -0,0 +1,185 @@
+use crate::error::Error;
+use serde::{Serialize, Deserialize};
+use serde_json::{Map, Value};
+use crate::bios::registry::{attribute_registry, AttributeRegistry};
+use crate::client::RedfishClient;
+use crate::discovery::Link;
+use crate::output::{or_na, table, value_to_string, Render};
+
+/// The Bios resource of a system, holding the current attribute values.
+#[derive(Debug, Deserialize)]
+pub(crate) struct BiosResource {
+    #[serde(rename="AttributeRegistry")]
+    pub registry: Option<String>,
+    #[serde(rename="Attributes", default)]
+    pub attributes: Map<String, Value>,
+    #[serde(rename="@Redfish.Settings")]
+    pub settings: Option<SettingsInfo>,
+}
+
+/// Where pending values go, and when they may be applied.
+#[derive(Debug, Deserialize)]
+pub(crate) struct SettingsInfo {
+    #[serde(rename="SettingsObject")]
+    pub settings_object: Option<Link>,
+    #[serde(rename="SupportedApplyTimes", default)]
+    pub supported_apply_times: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PendingSettings {
+    #[serde(rename="Attributes", default)]
+    attributes: Map<String, Value>,
+}
+
+/// A BIOS attribute with its current value, the value waiting to be applied, and what the
+/// attribute registry says about it.
+#[derive(Debug, Serialize)]
+pub struct BiosAttribute {
+    #[serde(rename="Name")]
+    pub name: String,
+    #[serde(rename="CurrentValue")]
+    pub current_value: Value,
+    /// Value set through `Bios/Settings` that the next apply will switch to, if it differs.
+    #[serde(rename="PendingValue", skip_serializing_if="Option::is_none")]
+    pub pending_value: Option<Value>,
+    #[serde(rename="DisplayName", skip_serializing_if="Option::is_none")]
+    pub display_name: Option<String>,
+    #[serde(rename="HelpText", skip_serializing_if="Option::is_none")]
+    pub help_text: Option<String>,
+    #[serde(rename="Type", skip_serializing_if="Option::is_none")]
+    pub attribute_type: Option<String>,
+    #[serde(rename="AllowedValues", skip_serializing_if="Vec::is_empty")]
+    pub allowed_values: Vec<String>,
+    #[serde(rename="ReadOnly")]
+    pub read_only: bool,
+}
+
+/// Every BIOS attribute of a system.
+#[derive(Debug, Serialize)]
+#[serde(transparent)]
+pub struct BiosAttributes {
+    pub attributes: Vec<BiosAttribute>,
+}
+
+impl BiosAttribute {
+    pub(crate) fn pending_to_string(&self) -> String {
+        self.pending_value.as_ref().map(value_to_string).unwrap_or_default()
+    }
+
+    pub(crate) fn row(&self) -> Vec<String> {
+        vec![
+            self.name.to_owned(),
+            value_to_string(&self.current_value),
+            self.pending_to_string(),
+            self.allowed_values.join(" "),
+            self.display_name.to_owned().unwrap_or_default(),
+        ]
+    }
+}
+
+impl Render for BiosAttribute {
+    fn columns(&self) -> Vec<&'static str> {
+        vec!["Name", "CurrentValue", "PendingValue", "AllowedValues", "DisplayName"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        vec![self.row()]
+    }
+
+    fn human(&self) -> String {
+        let mut out = String::new();
+        out += &format!("Name:           {}\n", self.name);
+        out += &format!("Description:    {}\n", or_na(&self.display_name));
+        out += &format!("Current value:  {}\n", value_to_string(&self.current_value));
+        if self.pending_value.is_some() {
+            out += &format!("Pending value:  {}\n", self.pending_to_string());
+        }
+        out += &format!("Type:           {}\n", or_na(&self.attribute_type));
+        if !self.allowed_values.is_empty() {
+            out += &format!("Allowed values: {}\n", self.allowed_values.join(", "));
+        }
+        out += &format!("Read-only:      {}\n", self.read_only);
+        if let Some(help_text) = &self.help_text {
+            out += &format!("Help:           {}\n", help_text);
+        }
+        out
+    }
+}
+
+impl Render for BiosAttributes {
+    fn columns(&self) -> Vec<&'static str> {
+        vec!["Name", "CurrentValue", "PendingValue", "AllowedValues", "DisplayName"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        self.attributes.iter().map(BiosAttribute::row).collect()
+    }
+
+    fn human(&self) -> String {
+        table(&["Name", "Current", "Pending", "Allowed values", "Description"], &self.rows())
+    }
+}
+
+/// Current and pending BIOS values of the selected system, with their registry.
+pub(crate) struct BiosState {
+    pub path: String,
+    pub resource: BiosResource,
+    pub pending: Map<String, Value>,
+    pub registry: AttributeRegistry,
+}
+
+impl BiosState {
+    pub(crate) async fn load(client: &RedfishClient) -> Result<BiosState, Error> {
+        let path = client.bios().await?;
+        let resource: BiosResource = client.get(&path).await?;
+
+        let pending = match client.get::<PendingSettings>(&resource.settings_path(&path)).await {
+            Ok(settings) => settings.attributes,
+            Err(Error::NotFound(_)) => Map::new(),
+            Err(other) => return Err(other),
+        };
+        let registry = attribute_registry(resource.registry.as_deref(), &path, client).await?;
+        Ok(BiosState { path, resource, pending, registry })
+    }
+
+    pub(crate) fn attribute(&self, name: &str) -> Option<BiosAttribute> {
+        let current = self.resource.attributes.get(name)?;
+        let registry = self.registry.attribute(name);
+        Some(BiosAttribute {
+            name: name.to_owned(),
+            current_value: current.to_owned(),
+            pending_value: self.pending.get(name).filter(|pending| *pending != current).cloned(),
+            display_name: registry.and_then(|r| r.display_name.to_owned()),
+            help_text: registry.and_then(|r| r.help_text.to_owned()),
+            attribute_type: registry.and_then(|r| r.attribute_type.to_owned()),
+            allowed_values: registry.map(|r| r.allowed_values()).unwrap_or_default(),
+            read_only: registry.map(|r| r.read_only).unwrap_or(false),
+        })
+    }
+
+    pub(crate) fn attributes(&self) -> Vec<BiosAttribute> {
+        self.resource.attributes.keys().filter_map(|name| self.attribute(name)).collect()
+    }
+}
+
+impl BiosResource {
+    /// Path of the settings object pending values are written to, usually `Bios/Settings`.
+    pub(crate) fn settings_path(&self, bios: &str) -> String {
+        self.settings.as_ref()
+            .and_then(|s| s.settings_object.as_ref())
+            .map(|link| link.id.to_owned())
+            .unwrap_or_else(|| format!("{}/Settings", bios))
+    }
+}
+
+pub async fn list_bios_attributes(client: &RedfishClient) -> Result<BiosAttributes, Error> {
+    Ok(BiosAttributes { attributes: BiosState::load(client).await?.attributes() })
+}
+
+/// Lists only the attributes with a pending value that differs from the current one.
+pub async fn list_pending_bios_attributes(client: &RedfishClient) -> Result<BiosAttributes, Error> {
+    let mut attributes = list_bios_attributes(client).await?;
+    attributes.attributes.retain(|a| a.pending_value.is_some());
+    Ok(attributes)
+}
@@ -0,0 +1,143 @@
+use crate::error::Error;
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+use crate::client::RedfishClient;
+
+/// The attribute registry describing the BIOS attributes of a system.
+#[derive(Debug, Default, Deserialize)]
+pub struct AttributeRegistry {
+    #[serde(rename="RegistryEntries", default)]
+    pub entries: RegistryEntries,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct RegistryEntries {
+    #[serde(rename="Attributes", default)]
+    pub attributes: Vec<RegistryAttribute>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegistryAttribute {
+    #[serde(rename="AttributeName")]
+    pub name: String,
+    #[serde(rename="DisplayName")]
+    pub display_name: Option<String>,
+    #[serde(rename="HelpText")]
+    pub help_text: Option<String>,
+    /// `Enumeration`, `String`, `Integer`, `Boolean` or `Password`.
+    #[serde(rename="Type")]
+    pub attribute_type: Option<String>,
+    #[serde(rename="Value", default)]
+    pub values: Vec<RegistryValue>,
+    #[serde(rename="ReadOnly", default)]
+    pub read_only: bool,
+    #[serde(rename="LowerBound")]
+    pub lower_bound: Option<i64>,
+    #[serde(rename="UpperBound")]
+    pub upper_bound: Option<i64>,
+    #[serde(rename="MinLength")]
+    pub min_length: Option<usize>,
+    #[serde(rename="MaxLength")]
+    pub max_length: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegistryValue {
+    #[serde(rename="ValueName")]
+    pub name: String,
+    #[serde(rename="ValueDisplayName")]
+    pub display_name: Option<String>,
+}
+
+/// Registry file entry in the Registries collection, pointing at the registry itself.
+#[derive(Debug, Deserialize)]
+struct RegistryFile {
+    #[serde(rename="Location", default)]
+    location: Vec<RegistryLocation>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RegistryLocation {
+    #[serde(rename="Language")]
+    language: Option<String>,
+    #[serde(rename="Uri")]
+    uri: Option<String>,
+}
+
+impl AttributeRegistry {
+    pub fn attribute(&self, name: &str) -> Option<&RegistryAttribute> {
+        self.entries.attributes.iter().find(|a| a.name == name)
+    }
+}
+
+impl RegistryAttribute {
+    /// Names of the values an enumeration accepts.
+    pub fn allowed_values(&self) -> Vec<String> {
+        self.values.iter().map(|v| v.name.to_owned()).collect()
+    }
+
+    /// Turns a value typed on the command line into the JSON value the attribute expects,
+    /// refusing read-only attributes and values the registry does not allow.
+    pub fn parse(&self, raw: &str) -> Result<Value, Error> {
+        let invalid = |reason: String| Error::Usage(format!("invalid value '{}' for BIOS attribute {}: {}", raw, self.name, reason));
+        if self.read_only {
+            return Err(Error::Usage(format!("BIOS attribute {} is read-only", self.name)));
+        }
+
+        match self.attribute_type.as_deref() {
+            Some("Enumeration") => match self.values.iter().find(|v| v.name == raw) {
+                Some(value) => Ok(Value::String(value.name.to_owned())),
+                None => Err(invalid(format!("expected one of {}", self.allowed_values().join(", ")))),
+            },
+            Some("Integer") => {
+                let number: i64 = raw.parse().map_err(|_| invalid(String::from("expected an integer")))?;
+                if let Some(lower) = self.lower_bound.filter(|lower| number < *lower) {
+                    return Err(invalid(format!("expected at least {}", lower)));
+                }
+                if let Some(upper) = self.upper_bound.filter(|upper| number > *upper) {
+                    return Err(invalid(format!("expected at most {}", upper)));
+                }
+                Ok(Value::from(number))
+            }
+            Some("Boolean") => raw.parse::<bool>().map(Value::Bool).map_err(|_| invalid(String::from("expected true or false"))),
+            _ => {
+                let length = raw.chars().count();
+                if let Some(min) = self.min_length.filter(|min| length < *min) {
+                    return Err(invalid(format!("expected at least {} characters", min)));
+                }
+                if let Some(max) = self.max_length.filter(|max| length > *max) {
+                    return Err(invalid(format!("expected at most {} characters", max)));
+                }
+                Ok(Value::String(raw.to_owned()))
+            }
+        }
+    }
+}
+
+/// Fetches the attribute registry named by the Bios resource, e.g. `BiosAttributeRegistry.v1_0_3`.
+///
+/// Looks it up in the Registries collection first and falls back to `Bios/BiosRegistry`, where
+/// iDRAC also publishes it. Returns an empty registry when neither exists.
+pub(crate) async fn attribute_registry(name: Option<&str>, bios: &str, client: &RedfishClient) -> Result<AttributeRegistry, Error> {
+    if let Some(name) = name {
+        let file = client.get::<RegistryFile>(&format!("{}/{}", client.registries().await?, name)).await;
+        match file {
+            Ok(file) => {
+                let location = file.location.iter()
+                    .find(|l| l.language.as_deref() == Some("en"))
+                    .or_else(|| file.location.first())
+                    .and_then(|l| l.uri.to_owned());
+                if let Some(uri) = location {
+                    return client.get(&uri).await;
+                }
+            }
+            Err(Error::NotFound(_)) => {}
+            Err(other) => return Err(other),
+        }
+    }
+
+    match client.get(&format!("{}/BiosRegistry", bios)).await {
+        Err(Error::NotFound(_)) => Ok(AttributeRegistry::default()),
+        other => other,
+    }
+}
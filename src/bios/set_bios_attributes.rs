@@ -0,0 +1,174 @@
+use clap::ValueEnum;
+use crate::error::Error;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use crate::bios::list_bios_attributes::{BiosAttribute, BiosState};
+use crate::client::RedfishClient;
+use crate::output::{short_name, table, Render};
+
+/// When the iDRAC applies pending settings, as `@Redfish.SettingsApplyTime`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, ValueEnum)]
+pub enum ApplyTime {
+    /// Reboot now to apply the settings
+    Immediate,
+    /// Apply on the next reboot
+    #[default]
+    OnReset,
+    /// Reboot at the start of the maintenance window
+    AtMaintenanceWindowStart,
+    /// Apply on the first reboot within the maintenance window
+    InMaintenanceWindowOnReset,
+}
+
+impl ApplyTime {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ApplyTime::Immediate => "Immediate",
+            ApplyTime::OnReset => "OnReset",
+            ApplyTime::AtMaintenanceWindowStart => "AtMaintenanceWindowStart",
+            ApplyTime::InMaintenanceWindowOnReset => "InMaintenanceWindowOnReset",
+        }
+    }
+
+    fn needs_window(self) -> bool {
+        matches!(self, ApplyTime::AtMaintenanceWindowStart | ApplyTime::InMaintenanceWindowOnReset)
+    }
+}
+
+/// Maintenance window for the `AtMaintenanceWindowStart` and `InMaintenanceWindowOnReset` apply times.
+#[derive(Debug, Clone, Serialize)]
+pub struct MaintenanceWindow {
+    /// Start time in ISO 8601 format, e.g. `2026-10-20T22:00:00-05:00`.
+    #[serde(rename="MaintenanceWindowStartTime")]
+    pub start: String,
+    #[serde(rename="MaintenanceWindowDurationInSeconds")]
+    pub duration_in_seconds: u64,
+}
+
+/// BIOS changes written to the settings object, waiting to be applied.
+#[derive(Debug, Serialize)]
+pub struct BiosChanges {
+    #[serde(rename="Attributes")]
+    pub attributes: Vec<BiosAttribute>,
+    #[serde(rename="ApplyTime")]
+    pub apply_time: ApplyTime,
+    #[serde(rename="MaintenanceWindow", skip_serializing_if="Option::is_none")]
+    pub maintenance_window: Option<MaintenanceWindow>,
+    /// Job the iDRAC created to apply the changes, e.g. `JID_123456789012`.
+    #[serde(rename="Job", skip_serializing_if="Option::is_none")]
+    pub job: Option<String>,
+    /// State the job reached when it was waited for.
+    #[serde(rename="JobState", skip_serializing_if="Option::is_none")]
+    pub job_state: Option<String>,
+}
+
+impl Render for BiosChanges {
+    fn columns(&self) -> Vec<&'static str> {
+        vec!["Name", "CurrentValue", "PendingValue", "ApplyTime", "Job", "JobState"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        self.attributes.iter().map(|attribute| {
+            let row = attribute.row();
+            vec![
+                row[0].to_owned(),
+                row[1].to_owned(),
+                row[2].to_owned(),
+                self.apply_time.as_str().to_owned(),
+                self.job.to_owned().unwrap_or_default(),
+                self.job_state.to_owned().unwrap_or_default(),
+            ]
+        }).collect()
+    }
+
+    fn human(&self) -> String {
+        let rows: Vec<Vec<String>> = self.rows().into_iter().map(|row| row[..3].to_vec()).collect();
+        let mut out = table(&["Name", "Current", "Pending"], &rows);
+        out += &match (self.apply_time, &self.maintenance_window) {
+            (ApplyTime::Immediate, _) => String::from("Applying now, the system reboots\n"),
+            (ApplyTime::OnReset, _) => String::from("Applies on the next reboot\n"),
+            (ApplyTime::AtMaintenanceWindowStart, Some(window)) => {
+                format!("Applies at the start of the maintenance window at {}, the system reboots\n", window.start)
+            }
+            (ApplyTime::InMaintenanceWindowOnReset, Some(window)) => format!(
+                "Applies on the first reboot within {} seconds from {}\n", window.duration_in_seconds, window.start
+            ),
+            (_, None) => String::from("Applies in the next maintenance window\n"),
+        };
+        match (&self.job, &self.job_state) {
+            (Some(job), Some(state)) => out += &format!("Job: {} ({})\n", job, state),
+            (Some(job), None) => out += &format!("Job: {}\n", job),
+            _ => {}
+        }
+        out
+    }
+}
+
+/// Writes new values for BIOS attributes, given as name and value as typed on the command line.
+///
+/// Values are checked against the attribute registry before anything is sent; without a
+/// registry they are only converted to the type of the current value. The values stay pending
+/// until applied at `apply_time`.
+pub async fn set_bios_attributes(
+    changes: &[(String, String)],
+    apply_time: ApplyTime,
+    maintenance_window: Option<MaintenanceWindow>,
+    client: &RedfishClient,
+) -> Result<BiosChanges, Error> {
+    let mut state = BiosState::load(client).await?;
+
+    let mut attributes = Map::new();
+    for (name, raw) in changes {
+        let current = state.resource.attributes.get(name)
+            .ok_or_else(|| Error::NotFound(format!("BIOS attribute '{}'", name)))?;
+        let value = match state.registry.attribute(name) {
+            Some(registry) => registry.parse(raw)?,
+            None => like(current, name, raw)?,
+        };
+        attributes.insert(name.to_owned(), value);
+    }
+
+    let supported = state.resource.settings.as_ref().map(|s| s.supported_apply_times.as_slice()).unwrap_or_default();
+    if !supported.is_empty() && !supported.iter().any(|s| s == apply_time.as_str()) {
+        return Err(Error::Usage(format!(
+            "apply time {} is not supported by this system, it allows: {}", apply_time.as_str(), supported.join(", ")
+        )));
+    }
+    let mut settings_apply_time = json!({ "ApplyTime": apply_time });
+    match (&maintenance_window, apply_time.needs_window()) {
+        (Some(window), true) => {
+            settings_apply_time["MaintenanceWindowStartTime"] = json!(window.start);
+            settings_apply_time["MaintenanceWindowDurationInSeconds"] = json!(window.duration_in_seconds);
+        }
+        (None, true) => return Err(Error::Usage(format!("apply time {} needs a maintenance window", apply_time.as_str()))),
+        _ => {}
+    }
+
+    let settings = state.resource.settings_path(&state.path);
+    let response = client.patch(&settings, &json!({
+        "Attributes": attributes,
+        "@Redfish.SettingsApplyTime": settings_apply_time,
+    })).await?;
+    let job = response.headers().get("Location").and_then(|v| v.to_str().ok()).map(short_name);
+
+    for (name, value) in attributes {
+        state.pending.insert(name, value);
+    }
+    Ok(BiosChanges {
+        attributes: changes.iter().filter_map(|(name, _)| state.attribute(name)).collect(),
+        apply_time,
+        maintenance_window: maintenance_window.filter(|_| apply_time.needs_window()),
+        job,
+        job_state: None,
+    })
+}
+
+/// Converts `raw` to the JSON type of `current`, for services without an attribute registry.
+fn like(current: &Value, name: &str, raw: &str) -> Result<Value, Error> {
+    let invalid = |expected: &str| Error::Usage(format!("invalid value '{}' for BIOS attribute {}: expected {}", raw, name, expected));
+    match current {
+        Value::Number(_) => raw.parse::<i64>().map(Value::from).map_err(|_| invalid("an integer")),
+        Value::Bool(_) => raw.parse::<bool>().map(Value::Bool).map_err(|_| invalid("true or false")),
+        _ => Ok(Value::String(raw.to_owned())),
+    }
+}
@@ -0,0 +1,3 @@
+pub mod get_boot_settings;
+pub mod set_boot_order;
+pub mod set_boot_override;
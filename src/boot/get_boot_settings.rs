@@ -0,0 +1,159 @@
+use crate::error::Error;
+use serde::{Serialize, Deserialize};
+use crate::client::RedfishClient;
+use crate::discovery::Link;
+use crate::output::{or_na, Render};
+
+#[derive(Debug, Deserialize)]
+struct SystemBoot {
+    #[serde(rename="Boot")]
+    boot: Option<Boot>,
+}
+
+/// The `Boot` property of a computer system.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Boot {
+    /// Persistent boot order, as boot option references such as `Boot0001`.
+    #[serde(rename="BootOrder", default)]
+    pub boot_order: Vec<String>,
+    /// `Disabled`, `Once` or `Continuous`.
+    #[serde(rename="BootSourceOverrideEnabled")]
+    pub override_enabled: Option<String>,
+    #[serde(rename="BootSourceOverrideTarget")]
+    pub override_target: Option<String>,
+    /// `UEFI` or `Legacy`.
+    #[serde(rename="BootSourceOverrideMode")]
+    pub override_mode: Option<String>,
+    #[serde(rename="BootSourceOverrideTarget@Redfish.AllowableValues", default)]
+    pub allowed_targets: Vec<String>,
+    #[serde(rename="BootOptions", skip_serializing)]
+    pub(crate) boot_options: Option<Link>,
+}
+
+/// An entry of the boot order, e.g. a disk, a PXE capable NIC or an optical drive.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BootOption {
+    #[serde(rename="BootOptionReference")]
+    pub reference: String,
+    #[serde(rename="DisplayName")]
+    pub display_name: Option<String>,
+    #[serde(rename="BootOptionEnabled")]
+    pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BootOptionCollection {
+    #[serde(rename="Members", default)]
+    members: Vec<Link>,
+}
+
+/// Boot configuration of a system, with the boot options its boot order refers to.
+#[derive(Debug, Serialize)]
+pub struct BootSettings {
+    #[serde(rename="Boot")]
+    pub boot: Boot,
+    #[serde(rename="BootOptions")]
+    pub boot_options: Vec<BootOption>,
+    /// Whether the boot order was just changed and only takes effect once the iDRAC applied it.
+    #[serde(rename="Pending", skip_serializing_if="std::ops::Not::not")]
+    pub pending: bool,
+    /// Job the iDRAC created to apply a boot order change, e.g. `JID_123456789012`.
+    #[serde(rename="Job", skip_serializing_if="Option::is_none")]
+    pub job: Option<String>,
+    /// State the job reached when it was waited for.
+    #[serde(rename="JobState", skip_serializing_if="Option::is_none")]
+    pub job_state: Option<String>,
+}
+
+impl BootSettings {
+    pub fn option(&self, reference: &str) -> Option<&BootOption> {
+        self.boot_options.iter().find(|option| option.reference == reference)
+    }
+
+    /// Whether the boot order shown still waits to be applied, i.e. its job has not completed.
+    pub fn is_pending(&self) -> bool {
+        self.pending && self.job_state.as_deref() != Some("Completed")
+    }
+
+    fn boot_override(&self) -> String {
+        match self.boot.override_enabled.as_deref() {
+            Some("Disabled") | None => String::from("none"),
+            Some(enabled) => format!("{} ({})", or_na(&self.boot.override_target), enabled),
+        }
+    }
+}
+
+impl Render for BootSettings {
+    fn columns(&self) -> Vec<&'static str> {
+        vec![
+            "Position", "BootOptionReference", "DisplayName", "BootOptionEnabled",
+            "BootSourceOverrideTarget", "BootSourceOverrideEnabled", "BootSourceOverrideMode",
+        ]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        self.boot.boot_order.iter().enumerate().map(|(index, reference)| {
+            let option = self.option(reference);
+            vec![
+                (index + 1).to_string(),
+                reference.to_owned(),
+                or_na(&option.and_then(|o| o.display_name.as_ref())),
+                or_na(&option.and_then(|o| o.enabled)),
+                or_na(&self.boot.override_target),
+                or_na(&self.boot.override_enabled),
+                or_na(&self.boot.override_mode),
+            ]
+        }).collect()
+    }
+
+    fn human(&self) -> String {
+        let mut out = String::new();
+        out += &format!("Boot mode:       {}\n", or_na(&self.boot.override_mode));
+        out += &format!("Boot override:   {}\n", self.boot_override());
+        out += &format!("Allowed targets: {}\n", self.boot.allowed_targets.join(", "));
+        out += if self.is_pending() { "Boot order (pending until the BIOS job runs):\n" } else { "Boot order:\n" };
+        for (index, reference) in self.boot.boot_order.iter().enumerate() {
+            let option = self.option(reference);
+            let disabled = if option.and_then(|o| o.enabled) == Some(false) { " (disabled)" } else { "" };
+            out += &format!("  {}. {}  {}{}\n", index + 1, reference, or_na(&option.and_then(|o| o.display_name.as_ref())), disabled);
+        }
+        match (&self.job, &self.job_state) {
+            (Some(job), Some(state)) => out += &format!("Job: {} ({})\n", job, state),
+            (Some(job), None) => out += &format!("Job: {}\n", job),
+            _ => {}
+        }
+        out
+    }
+}
+
+/// Reads the `Boot` property of the selected system, with the system path and its `ETag`.
+pub(crate) async fn system_boot(client: &RedfishClient) -> Result<(String, Boot, Option<String>), Error> {
+    let system = client.system().await?;
+    let (resource, etag): (SystemBoot, _) = client.get_with_etag(&system).await?;
+    let boot = resource.boot.ok_or_else(|| Error::NotFound(format!("boot settings of system '{}'", system)))?;
+    Ok((system, boot, etag))
+}
+
+/// Reads the boot settings of the selected system, with the system path and its `ETag`.
+pub(crate) async fn boot_settings(client: &RedfishClient) -> Result<(String, BootSettings, Option<String>), Error> {
+    let (system, boot, etag) = system_boot(client).await?;
+    let collection = boot.boot_options.as_ref()
+        .map(|link| link.id.to_owned())
+        .unwrap_or_else(|| format!("{}/BootOptions", system));
+
+    // Older firmware has no BootOptions; the boot order then only shows references.
+    let members = match client.get::<BootOptionCollection>(&collection).await {
+        Ok(collection) => collection.members,
+        Err(Error::NotFound(_)) => Vec::new(),
+        Err(other) => return Err(other),
+    };
+    let mut boot_options = Vec::new();
+    for member in members {
+        boot_options.push(client.get(&member.id).await?);
+    }
+    Ok((system, BootSettings { boot, boot_options, pending: false, job: None, job_state: None }, etag))
+}
+
+pub async fn get_boot_settings(client: &RedfishClient) -> Result<BootSettings, Error> {
+    Ok(boot_settings(client).await?.1)
+}
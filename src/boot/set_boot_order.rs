@@ -0,0 +1,57 @@
+use crate::error::Error;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use crate::bios::list_bios_attributes::SettingsInfo;
+use crate::boot::get_boot_settings::{boot_settings, BootSettings};
+use crate::client::RedfishClient;
+use crate::output::short_name;
+
+#[derive(Debug, Deserialize)]
+struct SystemSettings {
+    #[serde(rename="@Redfish.Settings")]
+    settings: Option<SettingsInfo>,
+}
+
+/// Where a new boot order goes, with the `ETag` to send along: the settings object of the system,
+/// where the iDRAC creates a BIOS job to apply it, or the system itself when it has none.
+async fn settings_target(system: &str, etag: Option<String>, client: &RedfishClient) -> Result<(String, Option<String>), Error> {
+    let resource: SystemSettings = client.get(system).await?;
+    match resource.settings.and_then(|s| s.settings_object) {
+        Some(link) => {
+            let (_, etag): (Value, _) = client.get_with_etag(&link.id).await?;
+            Ok((link.id, etag))
+        }
+        None => Ok((system.to_owned(), etag)),
+    }
+}
+
+/// Moves the given boot options, by reference (`Boot0002`) or display name, to the front of the
+/// persistent boot order in the given order. The other entries keep their relative order.
+///
+/// The iDRAC applies the new order through a BIOS job on the next reboot, so the returned settings
+/// show the order that was sent as pending, with that job if the iDRAC reported one.
+pub async fn set_boot_order(first: &[String], client: &RedfishClient) -> Result<BootSettings, Error> {
+    let (system, mut settings, etag) = boot_settings(client).await?;
+    let boot = &settings.boot;
+
+    let mut order = Vec::new();
+    for wanted in first {
+        let reference = boot.boot_order.iter()
+            .find(|reference| *reference == wanted || settings.option(reference).and_then(|o| o.display_name.as_ref()) == Some(wanted))
+            .ok_or_else(|| Error::NotFound(format!("boot option '{}'", wanted)))?;
+        if order.contains(reference) {
+            return Err(Error::Usage(format!("boot option {} is given more than once", reference)));
+        }
+        order.push(reference.to_owned());
+    }
+    let rest: Vec<String> = boot.boot_order.iter().filter(|reference| !order.contains(reference)).cloned().collect();
+    order.extend(rest);
+
+    let (target, etag) = settings_target(&system, etag, client).await?;
+    let response = client.patch_if_match(&target, &json!({ "Boot": { "BootOrder": order } }), etag.as_deref()).await?;
+
+    settings.job = response.headers().get("Location").and_then(|v| v.to_str().ok()).map(short_name);
+    settings.boot.boot_order = order;
+    settings.pending = true;
+    Ok(settings)
+}
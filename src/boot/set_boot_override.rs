@@ -0,0 +1,68 @@
+use clap::ValueEnum;
+use crate::error::Error;
+use serde::Serialize;
+use serde_json::json;
+use crate::boot::get_boot_settings::{get_boot_settings, system_boot, BootSettings};
+use crate::client::RedfishClient;
+
+/// Device to boot from once, as `BootSourceOverrideTarget`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ValueEnum)]
+pub enum BootTarget {
+    /// Cancel a pending one-time boot
+    None,
+    /// Network boot
+    Pxe,
+    /// Optical drive or virtual media CD
+    Cd,
+    /// Local hard drive
+    Hdd,
+    /// BIOS setup screen
+    BiosSetup,
+    /// Lifecycle Controller
+    Utilities,
+    /// UEFI HTTP boot
+    UefiHttp,
+}
+
+/// Boot mode for the one-time boot, as `BootSourceOverrideMode`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ValueEnum)]
+pub enum BootMode {
+    #[serde(rename="UEFI")]
+    Uefi,
+    Legacy,
+}
+
+impl BootTarget {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            BootTarget::None => "None",
+            BootTarget::Pxe => "Pxe",
+            BootTarget::Cd => "Cd",
+            BootTarget::Hdd => "Hdd",
+            BootTarget::BiosSetup => "BiosSetup",
+            BootTarget::Utilities => "Utilities",
+            BootTarget::UefiHttp => "UefiHttp",
+        }
+    }
+}
+
+/// Makes the selected system boot from `target` on its next boot only, or cancels that with
+/// `BootTarget::None`. The persistent boot order is left alone.
+pub async fn set_boot_override(target: BootTarget, mode: Option<BootMode>, client: &RedfishClient) -> Result<BootSettings, Error> {
+    let (system, boot, etag) = system_boot(client).await?;
+    if !boot.allowed_targets.is_empty() && !boot.allowed_targets.iter().any(|t| t == target.as_str()) {
+        return Err(Error::Usage(format!(
+            "{} is not supported by this system, it allows: {}", target.as_str(), boot.allowed_targets.join(", ")
+        )));
+    }
+
+    let mut body = match target {
+        BootTarget::None => json!({ "BootSourceOverrideTarget": target, "BootSourceOverrideEnabled": "Disabled" }),
+        _ => json!({ "BootSourceOverrideTarget": target, "BootSourceOverrideEnabled": "Once" }),
+    };
+    if let Some(mode) = mode {
+        body["BootSourceOverrideMode"] = json!(mode);
+    }
+    client.patch_if_match(&system, &json!({ "Boot": body }), etag.as_deref()).await?;
+    get_boot_settings(client).await
+}
@@ -1 +1,2 @@
-pub mod get_chassis_info;
\ No newline at end of file
+pub mod get_chassis_info;
+pub mod set_indicator;
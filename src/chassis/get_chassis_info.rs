@@ -1,64 +1,73 @@
-use std::time::Duration;
-use reqwest::{Client, Error};
-use reqwest;
+use crate::error::Error;
 use serde::{Serialize, Deserialize};
-use crate::Settings;
+use serde_json::Value;
+use crate::client::RedfishClient;
+use crate::output::{or_na, Render};
 
 #[derive(Debug, Serialize, Deserialize)]
-struct ChassisInfo {
+pub struct ChassisInfo {
     #[serde(rename="IndicatorLED")]
-    indicator_led: String,
+    pub indicator_led: Option<String>,
     #[serde(rename="Manufacturer")]
-    manufacturer: String,
+    pub manufacturer: Option<String>,
     #[serde(rename="Model")]
-    model: String,
+    pub model: Option<String>,
     #[serde(rename="PartNumber")]
-    part_number: String,
+    pub part_number: Option<String>,
     #[serde(rename="PowerState")]
-    power_state: String,
+    pub power_state: Option<String>,
     #[serde(rename="SKU")]
-    sku: String,
+    pub sku: Option<String>,
     #[serde(rename="SerialNumber")]
-    serial_number: String,
-    #[serde(rename="Status")]
-    status: Status
+    pub serial_number: Option<String>,
+    #[serde(rename="Status", default)]
+    pub status: Status,
+    #[serde(rename="Oem", skip_serializing_if="Option::is_none")]
+    pub oem: Option<Value>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct Status {
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Status {
     #[serde(rename="Health")]
-    health: String,
+    pub health: Option<String>,
     #[serde(rename="State")]
-    state: String
+    pub state: Option<String>
 }
 
-#[tokio::main]
-pub async fn get_chassis_info(settings: Settings) -> Result<(), Error> {
-    let response = Client::builder()
-        .danger_accept_invalid_certs(true)
-        .timeout(Duration::from_secs(30))
-        .build()
-        .unwrap()
-        .get(format!("https://{}/redfish/v1/Chassis/System.Embedded.1", settings.host.to_owned()))
-        .basic_auth(settings.user, Some(settings.password))
-        .send()
-        .await
-        .unwrap();
+impl Render for ChassisInfo {
+    fn columns(&self) -> Vec<&'static str> {
+        vec!["IndicatorLED", "Manufacturer", "Model", "PartNumber", "SerialNumber", "SKU", "PowerState", "Health", "State"]
+    }
 
-    let response_json: ChassisInfo = match response.json().await {
-        Ok(r) => r,
-        Err(e) => panic!("Could not introspect the token. Error was:\n {:?}", e),
-    };
+    fn rows(&self) -> Vec<Vec<String>> {
+        vec![vec![
+            or_na(&self.indicator_led),
+            or_na(&self.manufacturer),
+            or_na(&self.model),
+            or_na(&self.part_number),
+            or_na(&self.serial_number),
+            or_na(&self.sku),
+            or_na(&self.power_state),
+            or_na(&self.status.health),
+            or_na(&self.status.state),
+        ]]
+    }
 
-    println!("Indicator LED: {}", response_json.indicator_led);
-    println!("Type:          {} {}", response_json.manufacturer, response_json.model);
-    println!("Part number:   {}", response_json.part_number);
-    println!("Serial number: {}", response_json.serial_number);
-    println!("SKU:           {}", response_json.sku);
-    println!("Power state:   {}", response_json.power_state);
-    if response_json.status.state == "Enabled" {
-        println!("Status:        {}", response_json.status.health);
+    fn human(&self) -> String {
+        let mut out = String::new();
+        out += &format!("Indicator LED: {}\n", or_na(&self.indicator_led));
+        out += &format!("Type:          {} {}\n", or_na(&self.manufacturer), or_na(&self.model));
+        out += &format!("Part number:   {}\n", or_na(&self.part_number));
+        out += &format!("Serial number: {}\n", or_na(&self.serial_number));
+        out += &format!("SKU:           {}\n", or_na(&self.sku));
+        out += &format!("Power state:   {}\n", or_na(&self.power_state));
+        if self.status.state.as_deref() == Some("Enabled") {
+            out += &format!("Status:        {}\n", or_na(&self.status.health));
+        }
+        out
     }
+}
 
-    Ok(())
-}
\ No newline at end of file
+pub async fn get_chassis_info(client: &RedfishClient) -> Result<ChassisInfo, Error> {
+    client.get(&client.chassis().await?).await
+}
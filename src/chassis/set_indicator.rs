@@ -0,0 +1,84 @@
+use std::time::Duration;
+use crate::error::Error;
+use serde::{Serialize, Deserialize};
+use serde_json::json;
+use crate::client::RedfishClient;
+use crate::output::{or_na, Render};
+
+/// State to put the chassis identify indicator in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Indicator {
+    On,
+    Off,
+    Blink,
+}
+
+/// The chassis identify indicator, through whichever property the service exposes:
+/// `LocationIndicatorActive` on current firmware, the deprecated `IndicatorLED` before that.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IndicatorStatus {
+    #[serde(rename="LocationIndicatorActive", skip_serializing_if="Option::is_none")]
+    pub location_indicator_active: Option<bool>,
+    #[serde(rename="IndicatorLED", skip_serializing_if="Option::is_none")]
+    pub indicator_led: Option<String>,
+}
+
+impl IndicatorStatus {
+    fn state(&self) -> String {
+        match (self.location_indicator_active, &self.indicator_led) {
+            (Some(true), _) => String::from("On"),
+            (Some(false), _) => String::from("Off"),
+            (None, led) => or_na(led),
+        }
+    }
+}
+
+impl Render for IndicatorStatus {
+    fn columns(&self) -> Vec<&'static str> {
+        vec!["LocationIndicatorActive", "IndicatorLED"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        vec![vec![or_na(&self.location_indicator_active), or_na(&self.indicator_led)]]
+    }
+
+    fn human(&self) -> String {
+        format!("Indicator LED: {}\n", self.state())
+    }
+}
+
+/// Sets the identify indicator of the selected chassis.
+///
+/// `LocationIndicatorActive` has no separate blinking state, so `On` and `Blink` both set it to
+/// true there.
+///
+/// With `duration`, the indicator is switched off again once it has passed, so this only returns
+/// then; Redfish has no timed identify. Should someone else change the chassis in the meantime,
+/// the indicator is left as it is and HTTP 412 reported.
+pub async fn set_indicator(indicator: Indicator, duration: Option<Duration>, client: &RedfishClient) -> Result<IndicatorStatus, Error> {
+    let chassis = client.chassis().await?;
+    let (current, etag): (IndicatorStatus, _) = client.get_with_etag(&chassis).await?;
+    patch_indicator(&current, indicator, &chassis, etag.as_deref(), client).await?;
+
+    if let Some(duration) = duration.filter(|_| indicator != Indicator::Off) {
+        let (lit, etag): (IndicatorStatus, _) = client.get_with_etag(&chassis).await?;
+        tokio::time::sleep(duration).await;
+        patch_indicator(&lit, Indicator::Off, &chassis, etag.as_deref(), client)
+            .await
+            .map_err(|e| e.precondition_failed_as("the chassis was changed by someone else in the meantime, the indicator was left as it is"))?;
+    }
+    client.get(&chassis).await
+}
+
+async fn patch_indicator(current: &IndicatorStatus, indicator: Indicator, chassis: &str, etag: Option<&str>, client: &RedfishClient) -> Result<(), Error> {
+    let body = match (current.location_indicator_active.is_some(), current.indicator_led.is_some()) {
+        (true, _) => json!({ "LocationIndicatorActive": indicator != Indicator::Off }),
+        (false, true) => json!({ "IndicatorLED": match indicator {
+            Indicator::On => "Lit",
+            Indicator::Off => "Off",
+            Indicator::Blink => "Blinking",
+        }}),
+        (false, false) => return Err(Error::NotFound(format!("indicator LED on chassis '{}'", chassis))),
+    };
+    client.patch_if_match(chassis, &body, etag).await.map(|_| ())
+}
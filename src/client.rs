@@ -0,0 +1,303 @@
+use std::time::Duration;
+use reqwest::header::{ETAG, IF_MATCH};
+use reqwest::multipart::Form;
+use reqwest::{Client, RequestBuilder, Response, StatusCode, Url};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_derive::Deserialize;
+use serde_json::json;
+use tokio::sync::{Mutex, OnceCell};
+use crate::auth::{self, AuthMode, Session};
+use crate::discovery::{Selector, ServiceRootLinks};
+use crate::error::Error;
+use crate::settings::Settings;
+use crate::tls;
+
+/// Session timeout assumed when the SessionService does not report one.
+const DEFAULT_SESSION_TIMEOUT: u64 = 1800;
+
+/// How long an upload may take, instead of the usual request timeout; firmware images can be
+/// hundreds of megabytes.
+const UPLOAD_TIMEOUT: Duration = Duration::from_secs(1800);
+
+/// Shared Redfish client, built once per invocation and handed to every command.
+///
+/// Owns the connection pool, the credentials and the base URL of the iDRAC so
+/// commands only have to care about the resource path they want to talk to.
+#[derive(Debug)]
+pub struct RedfishClient {
+    client: Client,
+    host: String,
+    base_url: String,
+    user: String,
+    password: String,
+    auth: AuthMode,
+    session_cache: bool,
+    session: Mutex<Option<Session>>,
+    pub(crate) selector: Selector,
+    pub(crate) service_root: OnceCell<ServiceRootLinks>,
+    pub(crate) system: OnceCell<String>,
+    pub(crate) chassis: OnceCell<String>,
+    pub(crate) manager: OnceCell<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SessionService {
+    #[serde(rename="SessionTimeout")]
+    session_timeout: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SessionResource {
+    #[serde(rename="@odata.id")]
+    id: Option<String>,
+}
+
+/// `https://<host>`, unless the host already carries a scheme (e.g. `http://127.0.0.1:8000` for a mock service).
+fn base_url(host: &str) -> String {
+    if host.contains("://") {
+        return host.trim_end_matches('/').to_owned();
+    }
+    format!("https://{}", host)
+}
+
+impl RedfishClient {
+    pub fn new(settings: &Settings) -> Result<RedfishClient, Error> {
+        let client = Client::builder()
+            .use_preconfigured_tls(tls::client_config(settings)?)
+            .timeout(Duration::from_secs(30))
+            .build()
+            .map_err(|e| Error::transport(&settings.host, e))?;
+
+        Ok(RedfishClient {
+            client,
+            host: settings.host.to_owned(),
+            base_url: base_url(&settings.host),
+            user: settings.user.to_owned(),
+            password: settings.password.clone().unwrap_or_default(),
+            auth: settings.auth,
+            session_cache: settings.session_cache,
+            session: Mutex::new(None),
+            selector: Selector { system: settings.system.to_owned(), manager: settings.manager.to_owned() },
+            service_root: OnceCell::new(),
+            system: OnceCell::new(),
+            chassis: OnceCell::new(),
+            manager: OnceCell::new(),
+        })
+    }
+
+    /// Turns a resource path (e.g. `/redfish/v1/Managers/iDRAC.Embedded.1`) into a full URL.
+    ///
+    /// Full URLs, as found in some `Location` headers, are only followed to the configured iDRAC
+    /// since the credentials go with every request. Of URLs with another scheme, host or port,
+    /// e.g. an address an iDRAC behind NAT knows itself by, just the path is kept.
+    pub fn url(&self, path: &str) -> String {
+        let Ok(url) = Url::parse(path) else {
+            return format!("{}{}", self.base_url, path);
+        };
+        let same_origin = Url::parse(&self.base_url).map(|base| base.origin() == url.origin()).unwrap_or(false);
+        match (same_origin, url.query()) {
+            (true, _) => path.to_owned(),
+            (false, Some(query)) => format!("{}{}?{}", self.base_url, url.path(), query),
+            (false, None) => format!("{}{}", self.base_url, url.path()),
+        }
+    }
+
+    /// Returns the session token to use, logging in or reusing a cached session as needed.
+    async fn token(&self) -> Result<String, Error> {
+        let mut session = self.session.lock().await;
+        if let Some(current) = session.as_ref() {
+            if !current.is_expired() {
+                return Ok(current.token.to_owned());
+            }
+        }
+
+        let cached = if self.session_cache { auth::load(&self.host, &self.user) } else { None };
+        let fresh = match cached {
+            Some(cached) => cached,
+            None => self.login().await?,
+        };
+        let token = fresh.token.to_owned();
+        *session = Some(fresh);
+        Ok(token)
+    }
+
+    /// Creates a new session through the SessionService.
+    async fn login(&self) -> Result<Session, Error> {
+        let path = "/redfish/v1/SessionService/Sessions";
+        let response = self.client.post(self.url(path))
+            .json(&json!({ "UserName": self.user, "Password": self.password }))
+            .send()
+            .await
+            .map_err(|e| Error::transport(&self.host, e))?;
+        let response = self.check(path, response).await?;
+
+        let header = |name: &str| response.headers().get(name).and_then(|v| v.to_str().ok()).map(str::to_owned);
+        let token = header("X-Auth-Token")
+            .ok_or_else(|| Error::Redfish { status: response.status().as_u16(), messages: vec![String::from("login did not return an X-Auth-Token")] })?;
+        let location = header("Location");
+        let body: SessionResource = response.json().await.unwrap_or(SessionResource { id: None });
+        let location = location.or(body.id).unwrap_or_default();
+
+        let service = self.client.get(self.url("/redfish/v1/SessionService"))
+            .header("X-Auth-Token", &token)
+            .send()
+            .await
+            .ok()
+            .filter(|r| r.status().is_success());
+        let timeout = match service {
+            Some(r) => r.json::<SessionService>().await.ok().and_then(|s| s.session_timeout),
+            None => None,
+        };
+
+        Ok(Session::new(token, location, timeout.unwrap_or(DEFAULT_SESSION_TIMEOUT)))
+    }
+
+    /// Turns error statuses into an [`Error`].
+    async fn check(&self, path: &str, response: Response) -> Result<Response, Error> {
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+        let body = response.bytes().await.map_err(|e| Error::transport(&self.host, e))?;
+        Err(Error::from_status(path, status.as_u16(), &body))
+    }
+
+    /// Authenticates and sends a request, turning transport failures and error statuses into an [`Error`].
+    ///
+    /// A session the iDRAC no longer knows about, because it expired or the iDRAC restarted, is
+    /// replaced by a fresh one and the request retried once.
+    async fn send(&self, path: &str, builder: RequestBuilder) -> Result<Response, Error> {
+        let response = match self.auth {
+            AuthMode::Basic => builder.basic_auth(&self.user, Some(&self.password)).send().await,
+            AuthMode::Session => {
+                let retry = builder.try_clone();
+                let token = self.token().await?;
+                let response = builder.header("X-Auth-Token", token).send().await;
+
+                match (response, retry) {
+                    (Ok(r), Some(retry)) if r.status() == StatusCode::UNAUTHORIZED && self.drop_session().await => {
+                        let token = self.token().await?;
+                        retry.header("X-Auth-Token", token).send().await
+                    }
+                    (response, _) => response,
+                }
+            }
+        }.map_err(|e| Error::transport(&self.host, e))?;
+
+        if let Some(session) = self.session.lock().await.as_mut() {
+            session.touch();
+        }
+        self.check(path, response).await
+    }
+
+    /// Forgets the current session, and removes it from the cache if it came from there.
+    /// Returns whether there was one.
+    async fn drop_session(&self) -> bool {
+        match self.session.lock().await.take() {
+            Some(session) => {
+                if session.cached {
+                    auth::remove(&self.host, &self.user);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Fetches a resource and deserializes it into `T`.
+    pub async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T, Error> {
+        self.get_with_etag(path).await.map(|(value, _)| value)
+    }
+
+    /// Like [`get`](Self::get), also returning the `ETag` of the resource to hand back to
+    /// [`patch_if_match`](Self::patch_if_match).
+    pub async fn get_with_etag<T: DeserializeOwned>(&self, path: &str) -> Result<(T, Option<String>), Error> {
+        let response = self.send(path, self.client.get(self.url(path))).await?;
+        let etag = response.headers().get(ETAG).and_then(|v| v.to_str().ok()).map(str::to_owned);
+        let body = response.bytes().await.map_err(|e| Error::transport(&self.host, e))?;
+
+        let value = serde_json::from_slice(&body).map_err(|source| Error::Schema { path: path.to_owned(), source })?;
+        Ok((value, etag))
+    }
+
+    /// Fetches a resource without interpreting it, for responses whose meaning depends on the
+    /// status code, such as task monitors answering 202 while the task runs.
+    pub(crate) async fn get_raw(&self, path: &str) -> Result<(StatusCode, Vec<u8>), Error> {
+        let response = self.send(path, self.client.get(self.url(path))).await?;
+        let status = response.status();
+        let body = response.bytes().await.map_err(|e| Error::transport(&self.host, e))?;
+        Ok((status, body.to_vec()))
+    }
+
+    pub async fn patch<B: Serialize + ?Sized>(&self, path: &str, body: &B) -> Result<Response, Error> {
+        self.patch_if_match(path, body, None).await
+    }
+
+    /// Patches a resource only if it still has the given `ETag`, so concurrent changes are not
+    /// overwritten. The iDRAC answers HTTP 412 when the resource changed in the meantime.
+    pub async fn patch_if_match<B: Serialize + ?Sized>(&self, path: &str, body: &B, etag: Option<&str>) -> Result<Response, Error> {
+        let mut builder = self.client.patch(self.url(path)).json(body);
+        if let Some(etag) = etag {
+            builder = builder.header(IF_MATCH, etag);
+        }
+        self.send(path, builder).await
+    }
+
+    pub async fn post<B: Serialize + ?Sized>(&self, path: &str, body: &B) -> Result<Response, Error> {
+        self.send(path, self.client.post(self.url(path)).json(body)).await
+    }
+
+    /// Uploads a multipart form, e.g. a firmware image to the `MultipartHttpPushUri`.
+    ///
+    /// Parts built from files are read while the request is sent instead of being loaded into
+    /// memory. Such a body cannot be sent twice, so unlike other requests this one is not retried
+    /// when the session turns out to be gone.
+    pub async fn post_multipart(&self, path: &str, form: Form) -> Result<Response, Error> {
+        self.send(path, self.client.post(self.url(path)).multipart(form).timeout(UPLOAD_TIMEOUT)).await
+    }
+
+    pub async fn delete(&self, path: &str) -> Result<Response, Error> {
+        self.send(path, self.client.delete(self.url(path))).await
+    }
+
+    /// Finishes the invocation: keeps the session in the cache for the next run, or
+    /// deletes it on the iDRAC when caching is disabled.
+    pub async fn close(&self) -> Result<(), Error> {
+        let session = self.session.lock().await.take();
+        match session {
+            Some(session) if self.session_cache => auth::store(&self.host, &self.user, &session),
+            Some(session) => self.delete_session(&session).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Deletes the current or cached session on the iDRAC. Returns whether there was one.
+    pub async fn logout(&self) -> Result<bool, Error> {
+        let session = self.session.lock().await.take();
+        let session = session.or_else(|| auth::load(&self.host, &self.user));
+        auth::remove(&self.host, &self.user);
+
+        match session {
+            Some(session) => self.delete_session(&session).await.map(|_| true),
+            None => Ok(false),
+        }
+    }
+
+    async fn delete_session(&self, session: &Session) -> Result<(), Error> {
+        if session.location.is_empty() {
+            return Ok(());
+        }
+        let response = self.client.delete(self.url(&session.location))
+            .header("X-Auth-Token", &session.token)
+            .send()
+            .await
+            .map_err(|e| Error::transport(&self.host, e))?;
+
+        // The session already being gone is as good as deleting it.
+        match response.status() {
+            StatusCode::UNAUTHORIZED | StatusCode::NOT_FOUND => Ok(()),
+            _ => self.check(&session.location, response).await.map(|_| ()),
+        }
+    }
+}
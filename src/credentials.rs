@@ -0,0 +1,73 @@
+use std::fs;
+use std::io::IsTerminal;
+use std::path::Path;
+use std::process::Command;
+use crate::error::Error;
+use crate::settings::Settings;
+
+/// Settings keys that provide the password. A profile setting one of them replaces
+/// whichever one it would otherwise inherit from the defaults.
+pub const PASSWORD_KEYS: [&str; 4] = ["password", "password_env", "password_file", "password_command"];
+
+/// Works out the password from the first configured source: `password`, `password_env`,
+/// `password_file`, `password_command`, or an interactive prompt when none is set.
+pub fn resolve_password(settings: &Settings) -> Result<String, Error> {
+    if let Some(password) = &settings.password {
+        return Ok(password.to_owned());
+    }
+    if let Some(variable) = &settings.password_env {
+        return std::env::var(variable)
+            .map_err(|_| Error::Config(format!("environment variable {} from password_env is not set", variable)));
+    }
+    if let Some(file) = &settings.password_file {
+        return read_password_file(file);
+    }
+    if let Some(command) = &settings.password_command {
+        return run_password_command(command);
+    }
+
+    if !std::io::stdin().is_terminal() {
+        return Err(Error::Config(format!("no password configured for {}@{}", settings.user, settings.host)));
+    }
+    rpassword::prompt_password(format!("Password for {}@{}: ", settings.user, settings.host))
+        .map_err(|source| Error::Io { context: String::from("could not read password"), source })
+}
+
+fn read_password_file(file: &Path) -> Result<String, Error> {
+    if is_readable_by_others(file, 0o077) {
+        return Err(Error::Config(format!("password_file {} must not be accessible by group or others, run `chmod 600` on it", file.display())));
+    }
+    let content = fs::read_to_string(file)
+        .map_err(|source| Error::Io { context: format!("could not read password_file {}", file.display()), source })?;
+    Ok(content.trim_end_matches(['\r', '\n']).to_owned())
+}
+
+fn run_password_command(command: &str) -> Result<String, Error> {
+    let output = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .output()
+        .map_err(|source| Error::Io { context: format!("could not run password_command `{}`", command), source })?;
+    if !output.status.success() {
+        return Err(Error::Config(format!(
+            "password_command `{}` failed with {}: {}", command, output.status, String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    let password = String::from_utf8(output.stdout)
+        .map_err(|_| Error::Config(format!("password_command `{}` did not print valid UTF-8", command)))?;
+    Ok(password.trim_end_matches(['\r', '\n']).to_owned())
+}
+
+/// Whether any of the permission bits in `mask` are set on `file`. Always false outside Unix.
+pub fn is_readable_by_others(file: &Path, mask: u32) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::metadata(file).map(|m| m.permissions().mode() & mask != 0).unwrap_or(false)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = (file, mask);
+        false
+    }
+}
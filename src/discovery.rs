@@ -0,0 +1,237 @@
+use serde_derive::Deserialize;
+use crate::client::RedfishClient;
+use crate::error::Error;
+use crate::output::short_name;
+
+/// The parts of the service root (`/redfish/v1`) used to find everything else.
+#[derive(Debug, Deserialize)]
+struct ServiceRoot {
+    #[serde(rename="Systems")]
+    systems: Option<Link>,
+    #[serde(rename="Chassis")]
+    chassis: Option<Link>,
+    #[serde(rename="Managers")]
+    managers: Option<Link>,
+    #[serde(rename="Registries")]
+    registries: Option<Link>,
+    #[serde(rename="UpdateService")]
+    update_service: Option<Link>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Link {
+    #[serde(rename="@odata.id")]
+    pub id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Collection {
+    #[serde(rename="Members", default)]
+    members: Vec<Link>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SystemLinks {
+    #[serde(rename="Links")]
+    links: Option<SystemLinksChassis>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SystemLinksChassis {
+    #[serde(rename="Chassis", default)]
+    chassis: Vec<Link>,
+}
+
+/// Links to the collections commands start from, as found on systems and chassis.
+#[derive(Debug, Deserialize)]
+struct ResourceLinks {
+    #[serde(rename="NetworkAdapters")]
+    network_adapters: Option<Link>,
+    #[serde(rename="Storage")]
+    storage: Option<Link>,
+    #[serde(rename="Bios")]
+    bios: Option<Link>,
+    #[serde(rename="Power")]
+    power: Option<Link>,
+    #[serde(rename="Thermal")]
+    thermal: Option<Link>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UpdateServiceLinks {
+    #[serde(rename="FirmwareInventory")]
+    firmware_inventory: Option<Link>,
+}
+
+/// Which system and manager to use when a service exposes more than one, e.g. `System.Embedded.1`.
+#[derive(Debug, Clone, Default)]
+pub struct Selector {
+    pub system: Option<String>,
+    pub manager: Option<String>,
+}
+
+impl RedfishClient {
+    async fn service_root(&self) -> Result<&ServiceRootLinks, Error> {
+        self.service_root.get_or_try_init(|| async {
+            let root: ServiceRoot = self.get("/redfish/v1").await?;
+            let missing = |name: &str| Error::NotFound(format!("{} collection in the service root", name));
+            Ok(ServiceRootLinks {
+                systems: root.systems.ok_or_else(|| missing("Systems"))?.id,
+                chassis: root.chassis.map(|l| l.id),
+                managers: root.managers.ok_or_else(|| missing("Managers"))?.id,
+                registries: root.registries.map(|l| l.id),
+                update_service: root.update_service.map(|l| l.id),
+            })
+        }).await
+    }
+
+    /// Path of the selected computer system, e.g. `/redfish/v1/Systems/System.Embedded.1`.
+    pub async fn system(&self) -> Result<String, Error> {
+        self.system.get_or_try_init(|| async {
+            let collection = self.service_root().await?.systems.to_owned();
+            self.member(&collection, self.selector.system.as_deref(), "system", "--system").await
+        }).await.cloned()
+    }
+
+    /// Path of the selected manager, e.g. `/redfish/v1/Managers/iDRAC.Embedded.1`.
+    pub async fn manager(&self) -> Result<String, Error> {
+        self.manager.get_or_try_init(|| async {
+            let collection = self.service_root().await?.managers.to_owned();
+            self.member(&collection, self.selector.manager.as_deref(), "manager", "--manager").await
+        }).await.cloned()
+    }
+
+    /// Path of the chassis holding the selected system, taken from the system's links and
+    /// falling back to the only member of the Chassis collection.
+    pub async fn chassis(&self) -> Result<String, Error> {
+        self.chassis.get_or_try_init(|| async {
+            let system: SystemLinks = self.get(&self.system().await?).await?;
+            if let Some(chassis) = system.links.and_then(|l| l.chassis.into_iter().next()) {
+                return Ok(chassis.id);
+            }
+            let collection = self.service_root().await?.chassis.to_owned()
+                .ok_or_else(|| Error::NotFound(String::from("Chassis collection in the service root")))?;
+            let members: Collection = self.get(&collection).await?;
+            let system = short_name(&self.system().await?);
+            match members.members.as_slice() {
+                [only] => Ok(only.id.to_owned()),
+                all => all.iter()
+                    .find(|m| short_name(&m.id) == system)
+                    .map(|m| m.id.to_owned())
+                    .ok_or_else(|| Error::NotFound(format!("chassis for system '{}'", system))),
+            }
+        }).await.cloned()
+    }
+
+    /// Path of the NetworkAdapters collection: linked from the chassis on current services,
+    /// and below the system on older iDRAC firmware.
+    pub async fn network_adapters(&self) -> Result<String, Error> {
+        let chassis: ResourceLinks = self.get(&self.chassis().await?).await?;
+        match chassis.network_adapters {
+            Some(link) => Ok(link.id),
+            None => Ok(format!("{}/NetworkAdapters", self.system().await?)),
+        }
+    }
+
+    /// Path of the Power resource of the selected chassis.
+    pub async fn power(&self) -> Result<String, Error> {
+        let chassis = self.chassis().await?;
+        let links: ResourceLinks = self.get(&chassis).await?;
+        match links.power {
+            Some(link) => Ok(link.id),
+            None => Ok(format!("{}/Power", chassis)),
+        }
+    }
+
+    /// Path of the Thermal resource of the selected chassis.
+    pub async fn thermal(&self) -> Result<String, Error> {
+        let chassis = self.chassis().await?;
+        let links: ResourceLinks = self.get(&chassis).await?;
+        match links.thermal {
+            Some(link) => Ok(link.id),
+            None => Ok(format!("{}/Thermal", chassis)),
+        }
+    }
+
+    /// Path of the Storage collection of the selected system.
+    pub async fn storage(&self) -> Result<String, Error> {
+        let system = self.system().await?;
+        let links: ResourceLinks = self.get(&system).await?;
+        match links.storage {
+            Some(link) => Ok(link.id),
+            None => Ok(format!("{}/Storage", system)),
+        }
+    }
+
+    /// Path of the Bios resource of the selected system.
+    pub async fn bios(&self) -> Result<String, Error> {
+        let system = self.system().await?;
+        let links: ResourceLinks = self.get(&system).await?;
+        match links.bios {
+            Some(link) => Ok(link.id),
+            None => Ok(format!("{}/Bios", system)),
+        }
+    }
+
+    /// Path of the Lifecycle Controller job queue of the selected manager.
+    pub async fn jobs(&self) -> Result<String, Error> {
+        Ok(format!("{}/Jobs", self.manager().await?))
+    }
+
+    /// Path of the LogServices collection of the selected manager, e.g. holding `Sel` and `Lclog`.
+    pub async fn log_services(&self) -> Result<String, Error> {
+        Ok(format!("{}/LogServices", self.manager().await?))
+    }
+
+    /// Path of the Registries collection, where attribute and message registries are published.
+    pub async fn registries(&self) -> Result<String, Error> {
+        let registries = self.service_root().await?.registries.to_owned();
+        Ok(registries.unwrap_or_else(|| String::from("/redfish/v1/Registries")))
+    }
+
+    /// Path of the UpdateService, which holds the firmware inventory and the update actions.
+    pub async fn update_service(&self) -> Result<String, Error> {
+        let update_service = self.service_root().await?.update_service.to_owned();
+        Ok(update_service.unwrap_or_else(|| String::from("/redfish/v1/UpdateService")))
+    }
+
+    /// Path of the FirmwareInventory collection, with one member per installed or staged image.
+    pub async fn firmware_inventory(&self) -> Result<String, Error> {
+        let update_service = self.update_service().await?;
+        let links: UpdateServiceLinks = self.get(&update_service).await?;
+        match links.firmware_inventory {
+            Some(link) => Ok(link.id),
+            None => Ok(format!("{}/FirmwareInventory", update_service)),
+        }
+    }
+
+    /// Picks a member of a collection, by id when `wanted` is given or the only member otherwise.
+    async fn member(&self, collection: &str, wanted: Option<&str>, kind: &str, flag: &str) -> Result<String, Error> {
+        let members: Collection = self.get(collection).await?;
+        let ids: Vec<String> = members.members.into_iter().map(|m| m.id).collect();
+
+        match wanted {
+            Some(wanted) => ids.into_iter()
+                .find(|id| id == wanted || short_name(id) == wanted)
+                .ok_or_else(|| Error::NotFound(format!("{} '{}'", kind, wanted))),
+            None => match ids.len() {
+                0 => Err(Error::NotFound(format!("any {} in {}", kind, collection))),
+                1 => Ok(ids.into_iter().next().unwrap_or_default()),
+                _ => Err(Error::Usage(format!(
+                    "found several {}s ({}), pick one with {}",
+                    kind, ids.iter().map(|id| short_name(id)).collect::<Vec<_>>().join(", "), flag
+                ))),
+            },
+        }
+    }
+}
+
+/// Collection paths from the service root, resolved once per client.
+#[derive(Debug)]
+pub(crate) struct ServiceRootLinks {
+    systems: String,
+    chassis: Option<String>,
+    managers: String,
+    registries: Option<String>,
+    update_service: Option<String>,
+}
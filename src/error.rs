@@ -0,0 +1,175 @@
+use std::error::Error as StdError;
+use serde_derive::Deserialize;
+use thiserror::Error;
+
+/// Exit code used for local failures, such as an unwritable cache directory.
+pub const EXIT_IO: i32 = 1;
+/// Exit code used for invalid or ambiguous command line arguments, same as clap uses.
+pub const EXIT_USAGE: i32 = 2;
+/// Exit code used when the settings file cannot be loaded.
+pub const EXIT_CONFIG: i32 = 3;
+/// Exit code used when the iDRAC cannot be reached.
+pub const EXIT_CONNECTION: i32 = 4;
+/// Exit code used when the TLS handshake fails.
+pub const EXIT_TLS: i32 = 5;
+/// Exit code used when the iDRAC rejects the credentials (HTTP 401/403).
+pub const EXIT_AUTH: i32 = 6;
+/// Exit code used when the requested resource does not exist (HTTP 404).
+pub const EXIT_NOT_FOUND: i32 = 7;
+/// Exit code used when the iDRAC answers with a Redfish error message.
+pub const EXIT_REDFISH: i32 = 8;
+/// Exit code used when a response does not match the expected schema.
+pub const EXIT_SCHEMA: i32 = 9;
+/// Exit code used when waiting for the iDRAC to reach a state takes too long.
+pub const EXIT_TIMEOUT: i32 = 10;
+/// Exit code used when a comparison finds differences, e.g. BIOS settings drifting from a baseline.
+pub const EXIT_DRIFT: i32 = 11;
+/// Exit code used when a job or task the iDRAC ran failed.
+pub const EXIT_FAILED: i32 = 12;
+
+/// Crate-wide error type.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("{0}")]
+    Usage(String),
+    #[error("could not load settings: {0}")]
+    Config(String),
+    #[error("{context}: {source}")]
+    Io { context: String, source: std::io::Error },
+    #[error("could not connect to {host}: {source}")]
+    Connection { host: String, source: reqwest::Error },
+    #[error("TLS handshake with {host} failed: {source}")]
+    Tls { host: String, source: reqwest::Error },
+    #[error("access denied by the iDRAC (HTTP {status}), check the configured user and password")]
+    Unauthorized { status: u16 },
+    #[error("{0} was not found")]
+    NotFound(String),
+    #[error("iDRAC returned HTTP {status}: {}", .messages.join("; "))]
+    Redfish { status: u16, messages: Vec<String> },
+    #[error("unexpected response from {path}: {source}")]
+    Schema { path: String, source: serde_json::Error },
+    #[error("{0}")]
+    Timeout(String),
+    #[error("{0}")]
+    Drift(String),
+    #[error("{0}")]
+    Failed(String),
+}
+
+impl Error {
+    /// Process exit code for this error, so scripts can tell failures apart.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Io { .. } => EXIT_IO,
+            Error::Usage(_) => EXIT_USAGE,
+            Error::Config(_) => EXIT_CONFIG,
+            Error::Connection { .. } => EXIT_CONNECTION,
+            Error::Tls { .. } => EXIT_TLS,
+            Error::Unauthorized { .. } => EXIT_AUTH,
+            Error::NotFound(_) => EXIT_NOT_FOUND,
+            Error::Redfish { .. } => EXIT_REDFISH,
+            Error::Schema { .. } => EXIT_SCHEMA,
+            Error::Timeout(_) => EXIT_TIMEOUT,
+            Error::Drift(_) => EXIT_DRIFT,
+            Error::Failed(_) => EXIT_FAILED,
+        }
+    }
+
+    /// Replaces the resource path of a `NotFound` error with a friendlier description,
+    /// e.g. `network adapter 'NIC.Slot.9'`.
+    pub fn not_found_as(self, what: impl Into<String>) -> Error {
+        match self {
+            Error::NotFound(_) => Error::NotFound(what.into()),
+            other => other,
+        }
+    }
+
+    /// Replaces the messages of an HTTP 412 Precondition Failed, which the iDRAC answers when a
+    /// resource changed since its `ETag` was read, with an explanation of what was not done.
+    pub fn precondition_failed_as(self, message: impl Into<String>) -> Error {
+        match self {
+            Error::Redfish { status: 412, .. } => Error::Redfish { status: 412, messages: vec![message.into()] },
+            other => other,
+        }
+    }
+
+    /// Whether the iDRAC could not be reached or was not ready to answer, as while it restarts.
+    pub fn is_unavailable(&self) -> bool {
+        matches!(self, Error::Connection { .. } | Error::Redfish { status: 502..=504, .. })
+    }
+
+    /// Classifies a transport error from reqwest as either a TLS or a connection failure.
+    pub(crate) fn transport(host: &str, source: reqwest::Error) -> Error {
+        let mut cause: Option<&(dyn StdError + 'static)> = source.source();
+        while let Some(err) = cause {
+            if is_tls(err) {
+                return Error::Tls { host: host.to_owned(), source };
+            }
+            cause = err.source();
+        }
+        Error::Connection { host: host.to_owned(), source }
+    }
+
+    /// Builds an error from a non-successful HTTP response body.
+    pub(crate) fn from_status(path: &str, status: u16, body: &[u8]) -> Error {
+        match status {
+            401 | 403 => Error::Unauthorized { status },
+            404 => Error::NotFound(format!("resource {}", path)),
+            _ => Error::Redfish { status, messages: extended_info(body) },
+        }
+    }
+}
+
+/// Whether `err` is a rustls error. Those reach reqwest wrapped in I/O errors, sometimes several
+/// times over, which do not list what they wrap as their source.
+fn is_tls(mut err: &(dyn StdError + 'static)) -> bool {
+    loop {
+        if err.is::<rustls::Error>() {
+            return true;
+        }
+        match err.downcast_ref::<std::io::Error>().and_then(std::io::Error::get_ref) {
+            Some(inner) => err = inner,
+            None => return false,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorResponse {
+    error: ErrorBody,
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorBody {
+    message: Option<String>,
+    #[serde(rename="@Message.ExtendedInfo", default)]
+    extended_info: Vec<ExtendedInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExtendedInfo {
+    #[serde(rename="Message")]
+    message: Option<String>,
+    #[serde(rename="Resolution")]
+    resolution: Option<String>,
+}
+
+/// Pulls the human readable messages out of a Redfish error response.
+fn extended_info(body: &[u8]) -> Vec<String> {
+    let response: ErrorResponse = match serde_json::from_slice(body) {
+        Ok(r) => r,
+        Err(_) => return vec![String::from_utf8_lossy(body).trim().to_owned()],
+    };
+
+    let mut messages: Vec<String> = response.error.extended_info.iter()
+        .filter_map(|info| match (&info.message, &info.resolution) {
+            (Some(message), Some(resolution)) => Some(format!("{} ({})", message, resolution)),
+            (Some(message), None) => Some(message.to_owned()),
+            _ => None,
+        })
+        .collect();
+    if messages.is_empty() {
+        messages.extend(response.error.message);
+    }
+    messages
+}
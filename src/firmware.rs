@@ -0,0 +1,3 @@
+pub mod check_firmware;
+pub mod list_firmware;
+pub mod update_firmware;
@@ -0,0 +1,292 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use crate::error::Error;
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+use crate::client::RedfishClient;
+use crate::firmware::list_firmware::{list_firmware, ComponentType, Firmware};
+use crate::output::{or_na, table, Render};
+
+/// A firmware package from a catalog: the version a component should run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Package {
+    pub name: Option<String>,
+    pub version: String,
+    /// Dell component ids of the devices the package is for, matched with `SoftwareId`.
+    pub component_ids: Vec<String>,
+    /// Dell system ids, e.g. `0716`, of the models the package is for; empty for any model.
+    pub systems: Vec<String>,
+    /// Where to get the package, to hand to `firmware update`.
+    pub location: Option<String>,
+}
+
+impl Package {
+    /// Packages without component ids, from manifests, match components by name instead.
+    fn applies_to(&self, firmware: &Firmware, system_id: Option<&str>) -> bool {
+        if let (false, Some(system_id)) = (self.systems.is_empty(), system_id) {
+            if !self.systems.iter().any(|system| system.eq_ignore_ascii_case(system_id)) {
+                return false;
+            }
+        }
+        match (self.component_ids.is_empty(), &self.name, &firmware.software_id) {
+            (true, Some(name), _) => or_na(&firmware.name).to_lowercase().contains(&name.to_lowercase()),
+            (false, _, Some(software_id)) => self.component_ids.contains(software_id),
+            _ => false,
+        }
+    }
+}
+
+/// Firmware versions to compare a host with.
+#[derive(Debug)]
+pub struct Catalog {
+    /// The file the catalog came from, for messages.
+    pub source: String,
+    pub packages: Vec<Package>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ManifestEntry {
+    name: Option<String>,
+    component_id: Option<Value>,
+    version: Value,
+    package: Option<String>,
+}
+
+impl Catalog {
+    /// Reads a Dell catalog (`Catalog.xml`, as published by Dell or exported from a Dell
+    /// repository), or a JSON or YAML manifest listing `name` or `component_id`, `version` and
+    /// optionally `package` for each component.
+    pub fn read(path: &Path) -> Result<Catalog, Error> {
+        let bytes = fs::read(path)
+            .map_err(|source| Error::Io { context: format!("could not read catalog {}", path.display()), source })?;
+        let invalid = |reason: String| Error::Usage(format!("invalid catalog {}: {}", path.display(), reason));
+
+        let text = decode(&bytes).ok_or_else(|| invalid(String::from("not UTF-8 or UTF-16 text")))?;
+        let packages = match text.trim_start().starts_with('<') {
+            true => Catalog::parse_xml(&text).map_err(|e| invalid(e.to_string()))?,
+            false => Catalog::parse_manifest(&text).map_err(invalid)?,
+        };
+        Ok(Catalog { source: path.display().to_string(), packages })
+    }
+
+    fn parse_xml(text: &str) -> Result<Vec<Package>, roxmltree::Error> {
+        let document = roxmltree::Document::parse(text)?;
+        let root = document.root_element();
+        let base = root.attribute("baseLocation").unwrap_or_default().trim_end_matches('/');
+        let location = |path: &str| match base {
+            "" => path.to_owned(),
+            base if base.contains("://") => format!("{}/{}", base, path),
+            base => format!("https://{}/{}", base, path),
+        };
+        let display = |node: roxmltree::Node, tag: &str| node.children()
+            .find(|child| child.has_tag_name(tag))
+            .and_then(|child| child.descendants().find(|d| d.has_tag_name("Display")))
+            .and_then(|d| d.text())
+            .map(|text| text.trim().to_owned());
+
+        // Packages for devices the catalog only identifies by PCI ids have no component id to
+        // match the inventory with, so they are left out.
+        Ok(root.descendants()
+            .filter(|node| node.has_tag_name("SoftwareComponent"))
+            .filter_map(|component| {
+                let version = component.attribute("vendorVersion").or(component.attribute("dellVersion"))?;
+                let attributes = |tag: &str, attribute: &str| component.descendants()
+                    .filter(|node| node.has_tag_name(tag))
+                    .filter_map(|node| node.attribute(attribute))
+                    .map(str::to_owned)
+                    .collect::<Vec<String>>();
+                let component_ids = attributes("Device", "componentID");
+                (!component_ids.is_empty()).then(|| Package {
+                    name: display(component, "Name"),
+                    version: version.to_owned(),
+                    component_ids,
+                    systems: attributes("Model", "systemID"),
+                    location: component.attribute("path").map(location),
+                })
+            })
+            .collect())
+    }
+
+    fn parse_manifest(text: &str) -> Result<Vec<Package>, String> {
+        let entries: Vec<ManifestEntry> = serde_yaml::from_str(text)
+            .map_err(|e| format!("expected a list of components: {}", e))?;
+        entries.into_iter().enumerate().map(|(index, entry)| {
+            let version = match entry.version {
+                Value::String(version) => version,
+                _ => return Err(format!("the version of entry {} is not a string, quote versions such as '6.10'", index + 1)),
+            };
+            let component_ids = match entry.component_id {
+                Some(Value::String(id)) => vec![id],
+                Some(Value::Number(id)) => vec![id.to_string()],
+                Some(_) => return Err(format!("the component_id of entry {} is not a string", index + 1)),
+                None if entry.name.is_some() => Vec::new(),
+                None => return Err(format!("entry {} needs a name or a component_id", index + 1)),
+            };
+            Ok(Package { name: entry.name, version, component_ids, systems: Vec::new(), location: entry.package })
+        }).collect()
+    }
+}
+
+/// Decodes text files, including the UTF-16 Dell publishes its catalogs in.
+fn decode(bytes: &[u8]) -> Option<String> {
+    let utf16 = |bytes: &[u8], from_bytes: fn([u8; 2]) -> u16| {
+        let units: Vec<u16> = bytes.chunks_exact(2).map(|pair| from_bytes([pair[0], pair[1]])).collect();
+        String::from_utf16(&units).ok()
+    };
+    match bytes {
+        [0xFF, 0xFE, rest @ ..] => utf16(rest, u16::from_le_bytes),
+        [0xFE, 0xFF, rest @ ..] => utf16(rest, u16::from_be_bytes),
+        [0xEF, 0xBB, 0xBF, rest @ ..] => String::from_utf8(rest.to_vec()).ok(),
+        _ => String::from_utf8(bytes.to_vec()).ok(),
+    }
+}
+
+/// Compares firmware versions segment by segment, numerically where both segments are numbers,
+/// e.g. `2.9.0` < `2.10.1` and `DL63` < `DL6B`.
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let segments = |version: &str| version.split(['.', '-', '_']).map(str::to_uppercase).collect::<Vec<String>>();
+    let (a, b) = (segments(a), segments(b));
+    a.iter().zip(&b)
+        .map(|(x, y)| match (x.parse::<u64>(), y.parse::<u64>()) {
+            (Ok(x), Ok(y)) => x.cmp(&y),
+            _ => x.cmp(y),
+        })
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or_else(|| a.len().cmp(&b.len()))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Compliance {
+    Current,
+    /// The installed version is older than the catalog's.
+    Outdated,
+    /// The installed version is newer than the catalog's, which is not a failure.
+    Newer,
+}
+
+impl fmt::Display for Compliance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Compliance::Current => "Current",
+            Compliance::Outdated => "Outdated",
+            Compliance::Newer => "Newer",
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ComponentCheck {
+    #[serde(rename="Name")]
+    pub name: Option<String>,
+    #[serde(rename="ComponentType")]
+    pub component_type: ComponentType,
+    #[serde(rename="InstalledVersion")]
+    pub installed_version: Option<String>,
+    #[serde(rename="CatalogVersion")]
+    pub catalog_version: String,
+    #[serde(rename="Compliance")]
+    pub compliance: Compliance,
+    #[serde(rename="Package")]
+    pub package: Option<String>,
+}
+
+/// Installed firmware compared with a catalog, for the components the catalog has packages for.
+#[derive(Debug, Serialize)]
+pub struct FirmwareCheck {
+    #[serde(rename="Catalog")]
+    pub catalog: String,
+    #[serde(rename="Components")]
+    pub components: Vec<ComponentCheck>,
+}
+
+impl FirmwareCheck {
+    pub fn outdated(&self) -> impl Iterator<Item = &ComponentCheck> {
+        self.components.iter().filter(|c| c.compliance == Compliance::Outdated)
+    }
+
+    /// Fails with a drift error when components are out of date, to gate maintenance windows.
+    pub fn check(&self) -> Result<(), Error> {
+        match self.outdated().count() {
+            0 => Ok(()),
+            count => Err(Error::Drift(format!("{} component(s) are older than in {}", count, self.catalog))),
+        }
+    }
+}
+
+impl Render for FirmwareCheck {
+    fn columns(&self) -> Vec<&'static str> {
+        vec!["Name", "ComponentType", "InstalledVersion", "CatalogVersion", "Compliance", "Package"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        self.components.iter().map(|component| vec![
+            or_na(&component.name),
+            component.component_type.as_str().to_owned(),
+            or_na(&component.installed_version),
+            component.catalog_version.to_owned(),
+            component.compliance.to_string(),
+            or_na(&component.package),
+        ]).collect()
+    }
+
+    fn human(&self) -> String {
+        let outdated: Vec<Vec<String>> = self.outdated().map(|component| vec![
+            component.component_type.as_str().to_owned(),
+            or_na(&component.name),
+            or_na(&component.installed_version),
+            component.catalog_version.to_owned(),
+            or_na(&component.package),
+        ]).collect();
+        if outdated.is_empty() {
+            return format!("All {} component(s) in {} are up to date\n", self.components.len(), self.catalog);
+        }
+        table(&["Type", "Name", "Installed", "Catalog", "Package"], &outdated)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SystemOem {
+    #[serde(rename="Oem")]
+    oem: Option<Value>,
+}
+
+/// Dell system id of the selected system as catalogs write it, e.g. `0716` for a PowerEdge R640.
+async fn system_id(client: &RedfishClient) -> Result<Option<String>, Error> {
+    let system: SystemOem = client.get(&client.system().await?).await?;
+    Ok(system.oem.as_ref()
+        .and_then(|oem| oem.pointer("/Dell/DellSystem/SystemID"))
+        .and_then(Value::as_u64)
+        .map(|id| format!("{:04X}", id)))
+}
+
+/// Compares the installed firmware of the selected system with `catalog`.
+///
+/// Packages for other models are skipped. When several packages match a component, the newest
+/// one counts. Components the catalog has no package for are left out.
+pub async fn check_firmware(catalog: &Catalog, client: &RedfishClient) -> Result<FirmwareCheck, Error> {
+    let system_id = system_id(client).await?;
+    let installed = list_firmware(&[], client).await?.firmware.into_iter().filter(Firmware::is_installed);
+
+    let components = installed.filter_map(|firmware| {
+        let package = catalog.packages.iter()
+            .filter(|package| package.applies_to(&firmware, system_id.as_deref()))
+            .max_by(|a, b| compare_versions(&a.version, &b.version))?;
+        let compliance = match compare_versions(firmware.version.as_deref().unwrap_or_default(), &package.version) {
+            Ordering::Less => Compliance::Outdated,
+            Ordering::Equal => Compliance::Current,
+            Ordering::Greater => Compliance::Newer,
+        };
+        Some(ComponentCheck {
+            name: firmware.name,
+            component_type: firmware.component_type,
+            installed_version: firmware.version,
+            catalog_version: package.version.to_owned(),
+            compliance,
+            package: package.location.to_owned(),
+        })
+    }).collect();
+
+    Ok(FirmwareCheck { catalog: catalog.source.to_owned(), components })
+}
@@ -0,0 +1,194 @@
+use clap::ValueEnum;
+use crate::error::Error;
+use serde::{Serialize, Deserialize};
+use crate::client::RedfishClient;
+use crate::discovery::Link;
+use crate::output::{or_na, table, Render};
+
+#[derive(Debug, Deserialize)]
+struct FirmwareCollection {
+    #[serde(rename="Members", default)]
+    members: Vec<Link>,
+}
+
+/// Kind of hardware a firmware image is for, guessed from its device (FQDD) and name.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, ValueEnum)]
+#[serde(rename_all="lowercase")]
+pub enum ComponentType {
+    Bios,
+    Idrac,
+    /// RAID and other storage controllers
+    Raid,
+    /// Network and Fibre Channel adapters
+    Nic,
+    Disk,
+    Psu,
+    Cpld,
+    /// Backplanes, drivers and anything else
+    #[default]
+    Other,
+}
+
+impl ComponentType {
+    /// Dell appends the FQDD of the device to inventory ids, e.g. `__RAID.Integrated.1-1`; ids
+    /// without one (BIOS, iDRAC) and other vendors fall back to the name.
+    fn of(id: &str, name: &str) -> ComponentType {
+        let device = id.split_once("__").map(|(_, fqdd)| fqdd).unwrap_or_default();
+        let by_device = [
+            ("Disk.", ComponentType::Disk),
+            ("RAID.", ComponentType::Raid),
+            ("AHCI.", ComponentType::Raid),
+            ("NonRAID.", ComponentType::Raid),
+            ("NIC.", ComponentType::Nic),
+            ("FC.", ComponentType::Nic),
+            ("InfiniBand.", ComponentType::Nic),
+            ("PSU.", ComponentType::Psu),
+            ("CPLD.", ComponentType::Cpld),
+            ("BIOS.", ComponentType::Bios),
+            ("iDRAC.", ComponentType::Idrac),
+        ];
+        if let Some((_, kind)) = by_device.iter().find(|(prefix, _)| device.starts_with(prefix)) {
+            return *kind;
+        }
+        if !device.is_empty() {
+            return ComponentType::Other;
+        }
+
+        let name = name.to_lowercase();
+        let by_name = [
+            (&["bios"][..], ComponentType::Bios),
+            (&["remote access controller", "idrac"][..], ComponentType::Idrac),
+            (&["cpld"][..], ComponentType::Cpld),
+            (&["power supply"][..], ComponentType::Psu),
+            (&["disk", "drive", "ssd"][..], ComponentType::Disk),
+            (&["perc", "raid", "hba", "boss"][..], ComponentType::Raid),
+            (&["ethernet", "network", "nic", "fibre channel"][..], ComponentType::Nic),
+        ];
+        by_name.iter()
+            .find(|(words, _)| words.iter().any(|word| name.contains(word)))
+            .map(|(_, kind)| *kind)
+            .unwrap_or_default()
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ComponentType::Bios => "BIOS",
+            ComponentType::Idrac => "iDRAC",
+            ComponentType::Raid => "RAID",
+            ComponentType::Nic => "NIC",
+            ComponentType::Disk => "Disk",
+            ComponentType::Psu => "PSU",
+            ComponentType::Cpld => "CPLD",
+            ComponentType::Other => "Other",
+        }
+    }
+}
+
+/// A firmware image in the inventory, as a `SoftwareInventory` resource.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Firmware {
+    #[serde(rename="Id")]
+    pub id: String,
+    #[serde(rename="Name")]
+    pub name: Option<String>,
+    #[serde(rename="Version")]
+    pub version: Option<String>,
+    /// Whether the image can be replaced through the UpdateService.
+    #[serde(rename="Updateable")]
+    pub updateable: Option<bool>,
+    #[serde(rename="ReleaseDate")]
+    pub release_date: Option<String>,
+    /// Dell component id, shared by all versions of the same component.
+    #[serde(rename="SoftwareId")]
+    pub software_id: Option<String>,
+    #[serde(rename="ComponentType", skip_deserializing)]
+    pub component_type: ComponentType,
+    /// `Installed`, `Previous` (kept for rollback) or `Available` (staged) on an iDRAC.
+    #[serde(rename="InstallState", skip_deserializing)]
+    pub install_state: Option<String>,
+}
+
+impl Firmware {
+    /// Fills in what the iDRAC only encodes in the id, e.g. `Previous-159-2.15.2`.
+    fn classify(mut self) -> Firmware {
+        self.component_type = ComponentType::of(&self.id, self.name.as_deref().unwrap_or_default());
+        self.install_state = self.id.split_once('-')
+            .map(|(state, _)| state)
+            .filter(|state| !state.is_empty() && state.chars().all(|c| c.is_ascii_alphabetic()))
+            .map(str::to_owned);
+        self
+    }
+
+    /// Identifies the component across versions: the Dell component id and device, e.g.
+    /// `108255__NIC.Integrated.1-1-1`, or the name on other services.
+    pub(crate) fn component(&self) -> String {
+        let device = self.id.split_once("__").map(|(_, fqdd)| format!("__{}", fqdd)).unwrap_or_default();
+        match &self.software_id {
+            Some(software_id) => format!("{}{}", software_id, device),
+            None => format!("{}{}", or_na(&self.name), device),
+        }
+    }
+
+    pub fn is_installed(&self) -> bool {
+        matches!(self.install_state.as_deref(), None | Some("Installed" | "Current"))
+    }
+
+    /// Release dates without a date, which the iDRAC reports for some devices, are left out.
+    fn release_day(&self) -> Option<&str> {
+        self.release_date.as_deref()
+            .filter(|date| date.len() >= 10 && !date.starts_with("00:"))
+            .map(|date| &date[..10])
+    }
+
+    pub(crate) fn row(&self) -> Vec<String> {
+        vec![
+            self.id.to_owned(),
+            self.component_type.as_str().to_owned(),
+            or_na(&self.name),
+            or_na(&self.version),
+            or_na(&self.updateable),
+            or_na(&self.install_state),
+            or_na(&self.release_day()),
+        ]
+    }
+}
+
+/// The firmware inventory of the service.
+#[derive(Debug, Serialize)]
+#[serde(transparent)]
+pub struct FirmwareInventory {
+    pub firmware: Vec<Firmware>,
+}
+
+impl Render for FirmwareInventory {
+    fn columns(&self) -> Vec<&'static str> {
+        vec!["Id", "ComponentType", "Name", "Version", "Updateable", "InstallState", "ReleaseDate"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        self.firmware.iter().map(Firmware::row).collect()
+    }
+
+    fn human(&self) -> String {
+        if self.firmware.is_empty() {
+            return String::from("No firmware found\n");
+        }
+        let rows: Vec<Vec<String>> = self.rows().into_iter()
+            .map(|row| row.into_iter().skip(1).collect())
+            .collect();
+        table(&["Type", "Name", "Version", "Updateable", "State", "Released"], &rows)
+    }
+}
+
+/// Reads every image in the firmware inventory, keeping those of the given types when any are given.
+pub async fn list_firmware(types: &[ComponentType], client: &RedfishClient) -> Result<FirmwareInventory, Error> {
+    let collection: FirmwareCollection = client.get(&client.firmware_inventory().await?).await?;
+    let mut firmware = Vec::new();
+    for member in collection.members {
+        let image = client.get::<Firmware>(&member.id).await?.classify();
+        if types.is_empty() || types.contains(&image.component_type) {
+            firmware.push(image);
+        }
+    }
+    Ok(FirmwareInventory { firmware })
+}
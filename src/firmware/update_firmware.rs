@@ -0,0 +1,261 @@
+use std::collections::HashSet;
+use std::convert::Infallible;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use clap::ValueEnum;
+use reqwest::multipart::{Form, Part};
+use crate::error::Error;
+use serde::{Serialize, Deserialize};
+use serde_json::json;
+use crate::client::RedfishClient;
+use crate::firmware::list_firmware::{list_firmware, ComponentType, Firmware};
+use crate::output::{or_na, table, Render};
+use crate::task::{task_location, Task, POLL_INTERVAL};
+
+#[derive(Debug, Deserialize)]
+struct UpdateService {
+    #[serde(rename="MultipartHttpPushUri")]
+    multipart_http_push_uri: Option<String>,
+    #[serde(rename="Actions", default)]
+    actions: UpdateActions,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct UpdateActions {
+    #[serde(rename="#UpdateService.SimpleUpdate")]
+    simple_update: Option<SimpleUpdateAction>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SimpleUpdateAction {
+    target: String,
+    #[serde(rename="TransferProtocol@Redfish.AllowableValues", default)]
+    transfer_protocols: Vec<String>,
+}
+
+/// When the iDRAC installs an update, as `@Redfish.OperationApplyTime`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, ValueEnum)]
+pub enum UpdateApplyTime {
+    /// Install right away, restarting the system if the component needs it
+    #[default]
+    Immediate,
+    /// Stage the update and install it on the next reset of the system
+    OnReset,
+}
+
+impl UpdateApplyTime {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            UpdateApplyTime::Immediate => "Immediate",
+            UpdateApplyTime::OnReset => "OnReset",
+        }
+    }
+}
+
+/// Firmware image to install: a local Dell Update Package, or a URI the iDRAC fetches itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Image {
+    File(PathBuf),
+    /// E.g. `https://repo.example.com/BIOS_1.2.3.EXE` or `nfs://10.0.0.5/share/BIOS_1.2.3.EXE`.
+    Uri(String),
+}
+
+impl FromStr for Image {
+    type Err = Infallible;
+
+    fn from_str(image: &str) -> Result<Image, Infallible> {
+        Ok(match image.contains("://") {
+            true => Image::Uri(image.to_owned()),
+            false => Image::File(PathBuf::from(image)),
+        })
+    }
+}
+
+impl Image {
+    fn label(&self) -> String {
+        match self {
+            Image::File(path) => path.display().to_string(),
+            Image::Uri(uri) => uri.to_owned(),
+        }
+    }
+}
+
+/// A component whose firmware changed with an update.
+#[derive(Debug, Serialize)]
+pub struct VersionChange {
+    #[serde(rename="Name")]
+    pub name: Option<String>,
+    #[serde(rename="ComponentType")]
+    pub component_type: ComponentType,
+    /// The installed version before the update, if the component had one.
+    #[serde(rename="Before")]
+    pub before: Option<String>,
+    #[serde(rename="After")]
+    pub after: Option<String>,
+    /// `Available` for updates staged until the next reset.
+    #[serde(rename="InstallState")]
+    pub install_state: Option<String>,
+}
+
+/// Outcome of a firmware update.
+#[derive(Debug, Serialize)]
+pub struct FirmwareUpdate {
+    #[serde(rename="Image")]
+    pub image: String,
+    #[serde(rename="ApplyTime")]
+    pub apply_time: UpdateApplyTime,
+    /// The task that ran the update, as last read.
+    #[serde(rename="Task", skip_serializing_if="Option::is_none")]
+    pub task: Option<Task>,
+    #[serde(rename="Changes")]
+    pub changes: Vec<VersionChange>,
+}
+
+impl FirmwareUpdate {
+    /// Whether the update still waits for the system to be reset.
+    pub fn is_pending(&self) -> bool {
+        self.task.as_ref().map(Task::is_pending).unwrap_or(false)
+            || self.changes.iter().any(|c| c.install_state.as_deref() == Some("Available"))
+    }
+}
+
+impl Render for FirmwareUpdate {
+    fn columns(&self) -> Vec<&'static str> {
+        vec!["Name", "ComponentType", "Before", "After", "InstallState"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        self.changes.iter().map(|change| vec![
+            or_na(&change.name),
+            change.component_type.as_str().to_owned(),
+            or_na(&change.before),
+            or_na(&change.after),
+            or_na(&change.install_state),
+        ]).collect()
+    }
+
+    fn human(&self) -> String {
+        let mut out = String::new();
+        if let Some(task) = &self.task {
+            out += &format!("Task:  {} ({})\n", or_na(&task.id.as_ref().or(task.uri.as_ref())), or_na(&task.task_state));
+        }
+        out += &format!("Image: {}\n", self.image);
+        if self.changes.is_empty() {
+            out += "No firmware version changed\n";
+        } else {
+            let rows: Vec<Vec<String>> = self.rows().into_iter()
+                .map(|row| vec![row[1].to_owned(), row[0].to_owned(), row[2].to_owned(), row[3].to_owned(), row[4].to_owned()])
+                .collect();
+            out += &table(&["Type", "Name", "Before", "After", "State"], &rows);
+        }
+        if self.is_pending() {
+            out += "The update is installed on the next reset of the system\n";
+        }
+        out
+    }
+}
+
+/// Hands the image to the iDRAC. Returns where to follow the update, if it runs as a task.
+async fn start_update(image: &Image, apply_time: UpdateApplyTime, client: &RedfishClient) -> Result<Option<String>, Error> {
+    let service: UpdateService = client.get(&client.update_service().await?).await?;
+    let response = match image {
+        Image::File(path) => {
+            let push_uri = service.multipart_http_push_uri
+                .ok_or_else(|| Error::NotFound(String::from("MultipartHttpPushUri in the UpdateService")))?;
+            let unreadable = |source| Error::Io { context: format!("could not read firmware image {}", path.display()), source };
+            let file = tokio::fs::File::open(path).await.map_err(unreadable)?;
+            let length = file.metadata().await.map_err(unreadable)?.len();
+            let file_name = path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+
+            let parameters = json!({ "Targets": [], "@Redfish.OperationApplyTime": apply_time.as_str() });
+            // Packages can be hundreds of megabytes, so the file is sent as it is read.
+            let form = Form::new()
+                .part("UpdateParameters", Part::text(parameters.to_string()).mime_str("application/json").expect("valid MIME type"))
+                .part("UpdateFile", Part::stream_with_length(file, length).file_name(file_name).mime_str("application/octet-stream").expect("valid MIME type"));
+            client.post_multipart(&push_uri, form).await?
+        }
+        Image::Uri(uri) => {
+            let action = service.actions.simple_update
+                .ok_or_else(|| Error::NotFound(String::from("UpdateService.SimpleUpdate action")))?;
+            let protocol = uri.split_once("://").map(|(scheme, _)| scheme.to_uppercase()).unwrap_or_default();
+            if !action.transfer_protocols.is_empty() && !action.transfer_protocols.contains(&protocol) {
+                return Err(Error::Usage(format!(
+                    "transfer protocol {} is not supported by this iDRAC, it allows: {}", protocol, action.transfer_protocols.join(", ")
+                )));
+            }
+
+            let mut body = json!({ "ImageURI": uri, "@Redfish.OperationApplyTime": apply_time.as_str() });
+            if !action.transfer_protocols.is_empty() {
+                body["TransferProtocol"] = json!(protocol);
+            }
+            client.post(&action.target, &body).await?
+        }
+    };
+    Ok(task_location(&response))
+}
+
+/// Components whose installed version changed, or that got an update staged, between two reads
+/// of the inventory. The iDRAC puts the version in inventory ids, so updated images show up
+/// with new ids.
+fn changes(before: &[Firmware], after: &[Firmware]) -> Vec<VersionChange> {
+    let known: HashSet<&str> = before.iter().map(|f| f.id.as_str()).collect();
+    let installed = |component: &str| before.iter()
+        .find(|f| f.is_installed() && f.component() == component)
+        .and_then(|f| f.version.to_owned());
+
+    after.iter()
+        .filter(|f| f.is_installed() || f.install_state.as_deref() == Some("Available"))
+        .filter_map(|f| {
+            let previous = installed(&f.component());
+            let changed = !known.contains(f.id.as_str()) || (f.is_installed() && previous != f.version);
+            changed.then(|| VersionChange {
+                name: f.name.to_owned(),
+                component_type: f.component_type,
+                before: previous,
+                after: f.version.to_owned(),
+                install_state: f.install_state.to_owned(),
+            })
+        })
+        .collect()
+}
+
+/// Reads the inventory once the update is done. Updates of the iDRAC itself restart it, so it
+/// being unreachable only fails once `deadline` passes.
+async fn inventory_after_update(deadline: Instant, client: &RedfishClient) -> Result<Vec<Firmware>, Error> {
+    loop {
+        match list_firmware(&[], client).await {
+            Err(e) if e.is_unavailable() && Instant::now() < deadline => {
+                tokio::time::sleep(POLL_INTERVAL.min(deadline.saturating_duration_since(Instant::now()))).await;
+            }
+            Err(e) if e.is_unavailable() => {
+                return Err(Error::Timeout(format!("the firmware inventory could still not be read after the update: {}", e)));
+            }
+            result => return result.map(|inventory| inventory.firmware),
+        }
+    }
+}
+
+/// Installs a firmware image and follows the update, calling `progress` with every state read.
+///
+/// Immediate updates are followed until they finish, updates on reset until the iDRAC has
+/// staged them. Fails with [`Error::Failed`] when the update fails and with [`Error::Timeout`]
+/// when it is still going, or the iDRAC is still restarting, after `timeout`.
+pub async fn update_firmware(
+    image: &Image,
+    apply_time: UpdateApplyTime,
+    timeout: Duration,
+    progress: impl FnMut(&Task),
+    client: &RedfishClient,
+) -> Result<FirmwareUpdate, Error> {
+    let before = list_firmware(&[], client).await?.firmware;
+    let deadline = Instant::now() + timeout;
+    let task = match start_update(image, apply_time, client).await? {
+        Some(uri) if apply_time == UpdateApplyTime::OnReset => Some(client.wait_for_task_or_pending(&uri, timeout, progress).await?),
+        Some(uri) => Some(client.wait_for_task(&uri, timeout, progress).await?),
+        None => None,
+    };
+    let after = inventory_after_update(deadline, client).await?;
+
+    Ok(FirmwareUpdate { image: image.label(), apply_time, task, changes: changes(&before, &after) })
+}
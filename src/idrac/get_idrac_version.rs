@@ -1,33 +1,28 @@
-use std::time::Duration;
-use reqwest::{Client, Error};
-use reqwest;
+use crate::error::Error;
 use serde::{Serialize, Deserialize};
-use crate::Settings;
+use crate::client::RedfishClient;
+use crate::output::{or_na, Render};
 
 #[derive(Debug, Serialize, Deserialize)]
-struct VersionData {
+pub struct VersionData {
     #[serde(rename="FirmwareVersion")]
-    firmware_version: String,
+    pub firmware_version: Option<String>,
 }
 
-#[tokio::main]
-pub async fn get_idrac_version(settings: Settings) -> Result<(), Error> {
-    let response = Client::builder()
-        .danger_accept_invalid_certs(true)
-        .timeout(Duration::from_secs(30))
-        .build()
-        .unwrap()
-        .get(format!("https://{}/redfish/v1/Managers/iDRAC.Embedded.1", settings.host.to_owned()))
-        .basic_auth(settings.user, Some(settings.password))
-        .send()
-        .await
-        .unwrap();
+impl Render for VersionData {
+    fn columns(&self) -> Vec<&'static str> {
+        vec!["FirmwareVersion"]
+    }
 
-    let response_json: VersionData = match response.json().await {
-        Ok(r) => r,
-        Err(e) => panic!("Could not introspect the token. Error was:\n {:?}", e),
-    };
+    fn rows(&self) -> Vec<Vec<String>> {
+        vec![vec![or_na(&self.firmware_version)]]
+    }
 
-    println!("iDRAC firmware version: {}", response_json.firmware_version);
-    Ok(())
-}
\ No newline at end of file
+    fn human(&self) -> String {
+        format!("iDRAC firmware version: {}\n", or_na(&self.firmware_version))
+    }
+}
+
+pub async fn get_idrac_version(client: &RedfishClient) -> Result<VersionData, Error> {
+    client.get(&client.manager().await?).await
+}
@@ -0,0 +1,4 @@
+pub mod delete_jobs;
+pub mod get_job;
+pub mod list_jobs;
+pub mod wait_for_job;
@@ -0,0 +1,60 @@
+use crate::error::Error;
+use serde::Serialize;
+use serde_json::json;
+use crate::client::RedfishClient;
+use crate::jobs::get_job::job_path;
+use crate::jobs::list_jobs::list_jobs;
+use crate::output::Render;
+
+/// Jobs removed from the job queue.
+#[derive(Debug, Serialize)]
+#[serde(transparent)]
+pub struct DeletedJobs {
+    pub ids: Vec<String>,
+}
+
+impl Render for DeletedJobs {
+    fn columns(&self) -> Vec<&'static str> {
+        vec!["Id"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        self.ids.iter().map(|id| vec![id.to_owned()]).collect()
+    }
+
+    fn human(&self) -> String {
+        let mut out = format!("Deleted {} job(s)\n", self.ids.len());
+        for id in &self.ids {
+            out += &format!("- {}\n", id);
+        }
+        out
+    }
+}
+
+/// Deletes a job from the queue. Running jobs cannot be deleted; the iDRAC refuses that.
+pub async fn delete_job(id: &str, client: &RedfishClient) -> Result<DeletedJobs, Error> {
+    client.delete(&job_path(id, client).await?).await
+        .map_err(|e| e.not_found_as(format!("job '{}'", id)))?;
+    Ok(DeletedJobs { ids: vec![id.to_owned()] })
+}
+
+/// Empties the job queue, through the Dell job service when the iDRAC has one and by deleting
+/// the jobs one by one otherwise.
+pub async fn clear_jobs(client: &RedfishClient) -> Result<DeletedJobs, Error> {
+    let ids: Vec<String> = list_jobs(client).await?.jobs.into_iter().map(|job| job.id).collect();
+    if ids.is_empty() {
+        return Ok(DeletedJobs { ids });
+    }
+
+    let action = format!("{}/Oem/Dell/DellJobService/Actions/DellJobService.DeleteJobQueue", client.manager().await?);
+    match client.post(&action, &json!({ "JobID": "JID_CLEARALL" })).await {
+        Ok(_) => Ok(DeletedJobs { ids }),
+        Err(Error::NotFound(_)) => {
+            for id in &ids {
+                delete_job(id, client).await?;
+            }
+            Ok(DeletedJobs { ids })
+        }
+        Err(other) => Err(other),
+    }
+}
@@ -0,0 +1,88 @@
+use crate::error::Error;
+use serde::{Serialize, Deserialize};
+use crate::client::RedfishClient;
+use crate::output::{or_na, Render};
+
+/// A Lifecycle Controller job, e.g. applying BIOS settings or a firmware update.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Job {
+    #[serde(rename="Id")]
+    pub id: String,
+    #[serde(rename="Name")]
+    pub name: Option<String>,
+    #[serde(rename="JobType")]
+    pub job_type: Option<String>,
+    /// E.g. `Scheduled`, `Running`, `Completed` or `Failed`.
+    #[serde(rename="JobState")]
+    pub job_state: Option<String>,
+    #[serde(rename="PercentComplete")]
+    pub percent_complete: Option<u32>,
+    #[serde(rename="Message")]
+    pub message: Option<String>,
+    #[serde(rename="MessageId")]
+    pub message_id: Option<String>,
+    /// `TIME_NOW` for jobs that start as soon as possible.
+    #[serde(rename="StartTime")]
+    pub start_time: Option<String>,
+    #[serde(rename="CompletionTime")]
+    pub completion_time: Option<String>,
+}
+
+impl Job {
+    pub fn is_successful(&self) -> bool {
+        self.job_state.as_deref() == Some("Completed")
+    }
+
+    pub fn is_failed(&self) -> bool {
+        matches!(self.job_state.as_deref(), Some("Failed" | "CompletedWithErrors" | "Cancelled" | "Exception"))
+    }
+
+    /// Whether the job waits for the next reboot of the system, or for its maintenance window.
+    pub fn is_pending(&self) -> bool {
+        self.job_state.as_deref() == Some("Scheduled")
+    }
+
+    pub(crate) fn row(&self) -> Vec<String> {
+        vec![
+            self.id.to_owned(),
+            or_na(&self.name),
+            or_na(&self.job_type),
+            or_na(&self.job_state),
+            or_na(&self.percent_complete),
+            or_na(&self.message),
+        ]
+    }
+}
+
+impl Render for Job {
+    fn columns(&self) -> Vec<&'static str> {
+        vec!["Id", "Name", "JobType", "JobState", "PercentComplete", "Message"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        vec![self.row()]
+    }
+
+    fn human(&self) -> String {
+        let mut out = String::new();
+        out += &format!("Id:         {}\n", self.id);
+        out += &format!("Name:       {}\n", or_na(&self.name));
+        out += &format!("Type:       {}\n", or_na(&self.job_type));
+        out += &format!("State:      {}\n", or_na(&self.job_state));
+        out += &format!("Progress:   {}\n", or_na(&self.percent_complete.map(|p| format!("{}%", p))));
+        out += &format!("Message:    {}\n", or_na(&self.message));
+        out += &format!("Start:      {}\n", or_na(&self.start_time));
+        out += &format!("Completion: {}\n", or_na(&self.completion_time));
+        out
+    }
+}
+
+/// Path of a job in the job queue of the selected manager.
+pub(crate) async fn job_path(id: &str, client: &RedfishClient) -> Result<String, Error> {
+    Ok(format!("{}/{}", client.jobs().await?, id))
+}
+
+pub async fn get_job(id: &str, client: &RedfishClient) -> Result<Job, Error> {
+    client.get(&job_path(id, client).await?).await
+        .map_err(|e| e.not_found_as(format!("job '{}'", id)))
+}
@@ -0,0 +1,48 @@
+use crate::error::Error;
+use serde::{Serialize, Deserialize};
+use crate::client::RedfishClient;
+use crate::discovery::Link;
+use crate::jobs::get_job::Job;
+use crate::output::{table, Render};
+
+#[derive(Debug, Deserialize)]
+struct JobCollection {
+    #[serde(rename="Members", default)]
+    members: Vec<Link>,
+}
+
+/// The jobs in the queue of the selected manager.
+#[derive(Debug, Serialize)]
+#[serde(transparent)]
+pub struct Jobs {
+    pub jobs: Vec<Job>,
+}
+
+impl Render for Jobs {
+    fn columns(&self) -> Vec<&'static str> {
+        vec!["Id", "Name", "JobType", "JobState", "PercentComplete", "Message"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        self.jobs.iter().map(Job::row).collect()
+    }
+
+    fn human(&self) -> String {
+        if self.jobs.is_empty() {
+            return String::from("The job queue is empty\n");
+        }
+        let rows: Vec<Vec<String>> = self.rows().into_iter()
+            .map(|row| vec![row[0].to_owned(), row[1].to_owned(), row[3].to_owned(), row[4].to_owned(), row[5].to_owned()])
+            .collect();
+        table(&["Id", "Name", "State", "Percent", "Message"], &rows)
+    }
+}
+
+pub async fn list_jobs(client: &RedfishClient) -> Result<Jobs, Error> {
+    let collection: JobCollection = client.get(&client.jobs().await?).await?;
+    let mut jobs = Vec::new();
+    for member in collection.members {
+        jobs.push(client.get(&member.id).await?);
+    }
+    Ok(Jobs { jobs })
+}
@@ -0,0 +1,56 @@
+use std::time::{Duration, Instant};
+use crate::error::Error;
+use crate::client::RedfishClient;
+use crate::jobs::get_job::{get_job, Job};
+use crate::output::or_na;
+
+/// How often the job is polled while waiting for it to finish.
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Polls a job until it completes, calling `progress` with every state read.
+///
+/// Fails with [`Error::Failed`] when the job fails and with [`Error::Timeout`] when it is still
+/// going after `timeout`. Jobs scheduled for the next reboot only start once the system restarts.
+pub async fn wait_for_job(id: &str, timeout: Duration, progress: impl FnMut(&Job), client: &RedfishClient) -> Result<Job, Error> {
+    poll_job(id, timeout, Job::is_successful, progress, client).await
+}
+
+/// Like [`wait_for_job`], also returning once the job is scheduled, so changes applied on the
+/// next reboot or in a maintenance window do not keep the caller waiting until then.
+pub async fn wait_for_job_or_pending(id: &str, timeout: Duration, progress: impl FnMut(&Job), client: &RedfishClient) -> Result<Job, Error> {
+    poll_job(id, timeout, |job| job.is_successful() || job.is_pending(), progress, client).await
+}
+
+async fn poll_job(
+    id: &str,
+    timeout: Duration,
+    done: impl Fn(&Job) -> bool,
+    mut progress: impl FnMut(&Job),
+    client: &RedfishClient,
+) -> Result<Job, Error> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        let job = get_job(id, client).await?;
+        progress(&job);
+        if job.is_failed() {
+            let outcome = match job.job_state.as_deref() {
+                Some("CompletedWithErrors") => "completed with errors",
+                Some("Cancelled") => "was cancelled",
+                _ => "failed",
+            };
+            return Err(Error::Failed(format!("job {} {}: {}", id, outcome, or_na(&job.message))));
+        }
+        if done(&job) {
+            return Ok(job);
+        }
+
+        let now = Instant::now();
+        if now >= deadline {
+            return Err(Error::Timeout(format!(
+                "job {} is still {} at {}% after {} seconds",
+                id, or_na(&job.job_state), job.percent_complete.unwrap_or(0), timeout.as_secs()
+            )));
+        }
+        tokio::time::sleep(POLL_INTERVAL.min(deadline - now)).await;
+    }
+}
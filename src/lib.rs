@@ -1,5 +1,53 @@
+//! Async client library for Dell iDRAC and other Redfish services.
+//!
+//! Build a [`RedfishClient`] from [`Settings`] and call the functions in the command
+//! modules, which return typed models:
+//!
+//! ```no_run
+//! # async fn example() -> Result<(), idrac_cli::Error> {
+//! use idrac_cli::output::or_na;
+//! use idrac_cli::settings::Selection;
+//! use idrac_cli::{chassis, RedfishClient, Settings};
+//!
+//! let settings = Settings::load(&Selection { profile: Some(String::from("rack12-node3")), ..Default::default() })?;
+//! let client = RedfishClient::new(&settings)?;
+//! let info = chassis::get_chassis_info::get_chassis_info(&client).await?;
+//! println!("{} is {}", or_na(&info.serial_number), or_na(&info.power_state));
+//! client.close().await?;
+//! # Ok(())
+//! # }
+//! ```
+
 use std::cmp;
 
+pub mod auth;
+pub mod bios;
+pub mod boot;
+pub mod chassis;
+pub mod client;
+pub mod credentials;
+pub mod discovery;
+pub mod error;
+pub mod firmware;
+pub mod idrac;
+pub mod jobs;
+pub mod logs;
+pub mod network;
+pub mod output;
+pub mod paths;
+pub mod power;
+pub mod redundancy;
+pub mod session;
+pub mod settings;
+pub mod storage;
+pub mod task;
+pub mod thermal;
+pub mod tls;
+
+pub use client::RedfishClient;
+pub use error::Error;
+pub use settings::Settings;
+
 pub fn convert(num: f64) -> String {
     let negative = if num.is_sign_positive() { "" } else { "-" };
     let num = num.abs();
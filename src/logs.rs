@@ -0,0 +1,2 @@
+pub mod clear_log;
+pub mod list_log_entries;
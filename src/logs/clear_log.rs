@@ -0,0 +1,37 @@
+use crate::error::Error;
+use serde::Serialize;
+use serde_json::json;
+use crate::client::RedfishClient;
+use crate::logs::list_log_entries::{log_service, Log};
+use crate::output::Render;
+
+/// A log that was emptied.
+#[derive(Debug, Serialize)]
+pub struct ClearedLog {
+    #[serde(rename="Log")]
+    pub log: String,
+}
+
+impl Render for ClearedLog {
+    fn columns(&self) -> Vec<&'static str> {
+        vec!["Log"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        vec![vec![self.log.to_owned()]]
+    }
+
+    fn human(&self) -> String {
+        format!("Cleared the {}\n", self.log)
+    }
+}
+
+/// Deletes every entry of `log` through `LogService.ClearLog`. The iDRAC only allows that for
+/// the System Event Log; the Lifecycle log cannot be cleared.
+pub async fn clear_log(log: Log, client: &RedfishClient) -> Result<ClearedLog, Error> {
+    let (_, service) = log_service(log, client).await?;
+    let action = service.actions.clear_log
+        .ok_or_else(|| Error::Usage(format!("the {} cannot be cleared on this iDRAC", log.name())))?;
+    client.post(&action.target, &json!({})).await?;
+    Ok(ClearedLog { log: log.name().to_owned() })
+}
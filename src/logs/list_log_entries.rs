@@ -0,0 +1,220 @@
+use chrono::{DateTime, Duration, FixedOffset, Local, NaiveDate, NaiveDateTime, TimeZone};
+use clap::ValueEnum;
+use crate::error::Error;
+use serde::{Serialize, Deserialize};
+use crate::client::RedfishClient;
+use crate::discovery::Link;
+use crate::output::{highlighted_table, or_na, Level, Render};
+
+/// A log kept by the iDRAC, as a LogService below the manager.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Log {
+    /// System Event Log: hardware events such as failed fans, power supplies or memory
+    Sel,
+    /// Lifecycle log: configuration changes, firmware updates, jobs and logins
+    #[value(alias = "lclog")]
+    Lc,
+}
+
+impl Log {
+    fn service_id(self) -> &'static str {
+        match self {
+            Log::Sel => "Sel",
+            Log::Lc => "Lclog",
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Log::Sel => "System Event Log",
+            Log::Lc => "Lifecycle log",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct LogService {
+    #[serde(rename="Entries")]
+    entries: Option<Link>,
+    #[serde(rename="Actions", default)]
+    pub(crate) actions: LogServiceActions,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct LogServiceActions {
+    #[serde(rename="#LogService.ClearLog")]
+    pub(crate) clear_log: Option<ActionTarget>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct ActionTarget {
+    pub(crate) target: String,
+}
+
+/// Reads the LogService of `log`. Returns its path with it.
+pub(crate) async fn log_service(log: Log, client: &RedfishClient) -> Result<(String, LogService), Error> {
+    let path = format!("{}/{}", client.log_services().await?, log.service_id());
+    let service = client.get(&path).await.map_err(|e| e.not_found_as(format!("{} ({})", log.name(), path)))?;
+    Ok((path, service))
+}
+
+#[derive(Debug, Deserialize)]
+struct EntryCollection {
+    #[serde(rename="Members", default)]
+    members: Vec<LogEntry>,
+    /// Large logs are served in pages, each linking to the next.
+    #[serde(rename="Members@odata.nextLink")]
+    next_link: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogEntry {
+    #[serde(rename="Id")]
+    pub id: String,
+    #[serde(rename="Created")]
+    pub created: Option<String>,
+    /// `OK`, `Warning` or `Critical`.
+    #[serde(rename="Severity")]
+    pub severity: Option<String>,
+    #[serde(rename="MessageId")]
+    pub message_id: Option<String>,
+    #[serde(rename="Message")]
+    pub message: Option<String>,
+    #[serde(rename="SensorType", skip_serializing_if="Option::is_none")]
+    pub sensor_type: Option<String>,
+}
+
+impl LogEntry {
+    pub fn level(&self) -> Level {
+        Level::of_health(self.severity.as_deref())
+    }
+
+    pub fn created(&self) -> Option<DateTime<FixedOffset>> {
+        self.created.as_deref().and_then(|created| DateTime::parse_from_rfc3339(created).ok())
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.id.to_owned(),
+            or_na(&self.created),
+            or_na(&self.severity),
+            or_na(&self.message_id),
+            or_na(&self.message),
+        ]
+    }
+}
+
+/// Which entries to show.
+#[derive(Debug, Clone, Default)]
+pub struct LogFilter {
+    /// Only entries at least this severe.
+    pub severity: Option<Level>,
+    pub since: Option<DateTime<FixedOffset>>,
+    pub until: Option<DateTime<FixedOffset>>,
+    /// Message ids, in full (`IDRAC.2.8.PSU0003`) or without the registry prefix (`PSU0003`), and
+    /// prefixes ending with `*` (`PSU*`).
+    pub message_ids: Vec<String>,
+    /// Stop after this many matching entries.
+    pub limit: Option<usize>,
+}
+
+impl LogFilter {
+    pub fn matches(&self, entry: &LogEntry) -> bool {
+        if self.severity.map(|severity| entry.level() < severity).unwrap_or(false) {
+            return false;
+        }
+        if self.since.is_some() || self.until.is_some() {
+            // Entries without a usable time cannot be placed in the range.
+            let Some(created) = entry.created() else { return false };
+            if self.since.map(|since| created < since).unwrap_or(false) || self.until.map(|until| created > until).unwrap_or(false) {
+                return false;
+            }
+        }
+        self.message_ids.is_empty() || self.message_ids.iter().any(|wanted| message_id_matches(wanted, entry.message_id.as_deref()))
+    }
+}
+
+fn message_id_matches(wanted: &str, message_id: Option<&str>) -> bool {
+    let Some(message_id) = message_id else { return false };
+    let short = message_id.rsplit('.').next().unwrap_or(message_id);
+    match wanted.strip_suffix('*') {
+        Some(prefix) => [message_id, short].iter().any(|id| id.to_uppercase().starts_with(&prefix.to_uppercase())),
+        None => [message_id, short].iter().any(|id| id.eq_ignore_ascii_case(wanted)),
+    }
+}
+
+/// Parses `--since` and `--until` values: RFC 3339 timestamps, dates and times in local time
+/// such as `2026-10-18` or `2026-10-18 08:30`, or a time relative to now such as `90m`, `12h`
+/// or `7d`.
+pub fn parse_time(text: &str) -> Result<DateTime<FixedOffset>, String> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(text) {
+        return Ok(time);
+    }
+    let local = |naive: NaiveDateTime| Local.from_local_datetime(&naive).earliest().map(|time| time.fixed_offset());
+    for format in ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M"] {
+        if let Some(time) = NaiveDateTime::parse_from_str(text, format).ok().and_then(local) {
+            return Ok(time);
+        }
+    }
+    if let Some(time) = NaiveDate::parse_from_str(text, "%Y-%m-%d").ok().and_then(|date| date.and_hms_opt(0, 0, 0)).and_then(local) {
+        return Ok(time);
+    }
+
+    let (amount, unit) = text.split_at(text.char_indices().last().map(|(index, _)| index).unwrap_or(0));
+    let ago = match (amount.parse::<i64>().ok(), unit) {
+        (Some(amount), "s") => Duration::try_seconds(amount),
+        (Some(amount), "m") => Duration::try_minutes(amount),
+        (Some(amount), "h") => Duration::try_hours(amount),
+        (Some(amount), "d") => Duration::try_days(amount),
+        (Some(amount), "w") => Duration::try_weeks(amount),
+        _ => None,
+    };
+    ago.map(|ago| (Local::now() - ago).fixed_offset())
+        .ok_or_else(|| format!("'{}' is not a time, use e.g. 2026-10-18T08:30:00Z, 2026-10-18, 2026-10-18 08:30 or 12h", text))
+}
+
+/// Entries of a log, in the order the iDRAC returns them.
+#[derive(Debug, Serialize)]
+#[serde(transparent)]
+pub struct LogEntries {
+    pub entries: Vec<LogEntry>,
+}
+
+impl Render for LogEntries {
+    fn columns(&self) -> Vec<&'static str> {
+        vec!["Id", "Created", "Severity", "MessageId", "Message"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        self.entries.iter().map(LogEntry::row).collect()
+    }
+
+    fn human(&self) -> String {
+        if self.entries.is_empty() {
+            return String::from("No log entries found\n");
+        }
+        let rows: Vec<Vec<String>> = self.rows().into_iter().map(|row| row.into_iter().skip(1).collect()).collect();
+        let levels: Vec<Level> = self.entries.iter().map(LogEntry::level).collect();
+        highlighted_table(&["Created", "Severity", "MessageId", "Message"], &rows, &levels)
+    }
+}
+
+/// Reads the entries of `log` that match `filter`, following the pages of the log until the
+/// end or until `filter.limit` entries were found.
+pub async fn list_log_entries(log: Log, filter: &LogFilter, client: &RedfishClient) -> Result<LogEntries, Error> {
+    let (path, service) = log_service(log, client).await?;
+    let mut next = Some(service.entries.map(|link| link.id).unwrap_or_else(|| format!("{}/Entries", path)));
+    let mut entries = Vec::new();
+
+    while let Some(page) = next {
+        let collection: EntryCollection = client.get(&page).await?;
+        for entry in collection.members.into_iter().filter(|entry| filter.matches(entry)) {
+            entries.push(entry);
+            if filter.limit == Some(entries.len()) {
+                return Ok(LogEntries { entries });
+            }
+        }
+        next = collection.next_link;
+    }
+    Ok(LogEntries { entries })
+}
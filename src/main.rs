@@ -1,32 +1,87 @@
-use clap::{Args, Parser, Subcommand};
-use config::{Config};
-use serde_derive::Deserialize;
+use std::path::PathBuf;
+use std::process;
+use std::time::Duration;
+use chrono::{DateTime, FixedOffset};
+use clap::{ArgGroup, Args, Parser, Subcommand};
+use idrac_cli::client::RedfishClient;
+use idrac_cli::error::Error;
+use idrac_cli::output::{self, or_na, Format, Level, Progress, Render};
+use idrac_cli::settings::{Selection, Settings};
 
-mod idrac;
-mod chassis;
-mod lib;
-mod storage;
-mod network;
+use idrac_cli::idrac::get_idrac_version::get_idrac_version;
 
-use crate::idrac::get_idrac_version::get_idrac_version;
+use idrac_cli::bios::diff_bios_attributes::{diff_bios_attributes, Baseline};
+use idrac_cli::bios::get_bios_attribute::get_bios_attribute;
+use idrac_cli::bios::list_bios_attributes::{list_bios_attributes, list_pending_bios_attributes};
+use idrac_cli::bios::set_bios_attributes::{set_bios_attributes, ApplyTime, MaintenanceWindow};
 
-use crate::chassis::get_chassis_info::get_chassis_info;
+use idrac_cli::boot::get_boot_settings::get_boot_settings;
+use idrac_cli::boot::set_boot_order::set_boot_order;
+use idrac_cli::boot::set_boot_override::{set_boot_override, BootMode, BootTarget};
 
-use crate::network::list_network_adapters::list_network_adapters;
-use crate::network::get_network_adapter::get_network_adapter;
-use crate::network::get_network_port::get_network_port;
+use idrac_cli::chassis::get_chassis_info::get_chassis_info;
+use idrac_cli::chassis::set_indicator::{set_indicator, Indicator};
 
-use crate::storage::get_storage_controller::get_storage_controller;
-use crate::storage::get_storage_disk::get_storage_disk;
-use crate::storage::get_storage_volume::get_storage_volume;
-use crate::storage::list_storage_options::list_storage_controllers;
-use crate::storage::list_storage_volumes::list_storage_volumes;
+use idrac_cli::firmware::check_firmware::{check_firmware, Catalog};
+use idrac_cli::firmware::list_firmware::{list_firmware, ComponentType};
+use idrac_cli::firmware::update_firmware::{update_firmware, Image, UpdateApplyTime};
+
+use idrac_cli::jobs::delete_jobs::{clear_jobs, delete_job};
+use idrac_cli::jobs::get_job::{get_job, Job};
+use idrac_cli::jobs::list_jobs::list_jobs;
+use idrac_cli::jobs::wait_for_job::{wait_for_job, wait_for_job_or_pending};
+
+use idrac_cli::logs::clear_log::clear_log;
+use idrac_cli::logs::list_log_entries::{list_log_entries, parse_time, Log, LogFilter};
+
+use idrac_cli::network::list_network_adapters::list_network_adapters;
+use idrac_cli::network::get_network_adapter::get_network_adapter;
+use idrac_cli::network::get_network_port::get_network_port;
+
+use idrac_cli::power::get_power_cap::get_power_cap;
+use idrac_cli::power::get_power_status::get_power_status;
+use idrac_cli::power::list_reset_types::list_reset_types;
+use idrac_cli::power::reset_system::{reset_system, ResetType};
+use idrac_cli::power::set_power_cap::{clear_power_cap, set_power_cap, LimitException};
+
+use idrac_cli::thermal::get_thermal_info::get_thermal_info;
+
+use idrac_cli::storage::get_storage_controller::get_storage_controller;
+use idrac_cli::storage::get_storage_disk::get_storage_disk;
+use idrac_cli::storage::get_storage_volume::get_storage_volume;
+use idrac_cli::storage::list_storage_options::list_storage_controllers;
+use idrac_cli::storage::list_storage_volumes::list_storage_volumes;
+
+use idrac_cli::task;
+
+use idrac_cli::session::logout::logout;
 
 /// A simple command line interface for interacting with iDRAC
 #[derive(Debug, Parser)] // requires `derive` feature
 #[command(name = "idrac-cli")]
 #[command(about = "A simple command line interface for interacting with iDRAC", long_about = None)]
 struct Opts {
+    /// Settings file to use instead of the one found in the config directories
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+    /// Profile from the `[hosts.<name>]` section of the settings file
+    #[arg(long, global = true)]
+    profile: Option<String>,
+    /// iDRAC address, overriding the one from the settings file
+    #[arg(long, global = true)]
+    host: Option<String>,
+    /// Output format
+    #[arg(long, short, global = true, value_enum, default_value_t = Format::Table)]
+    output: Format,
+    /// Computer system to use when the iDRAC exposes several, e.g. `System.Embedded.1`
+    #[arg(long, global = true)]
+    system: Option<String>,
+    /// Manager to use when the service exposes several, e.g. `iDRAC.Embedded.1`
+    #[arg(long, global = true)]
+    manager: Option<String>,
+    /// Skip TLS certificate verification
+    #[arg(long, global = true)]
+    insecure: bool,
     #[command(subcommand)]
     command: Commands,
 }
@@ -34,16 +89,24 @@ struct Opts {
 #[derive(Debug, Subcommand)]
 enum Commands {
     Idrac(Idrac),
+    Bios(Bios),
+    Boot(Boot),
     Chassis(Chassis),
+    Firmware(Firmware),
+    Thermal(Thermal),
+    Jobs(Jobs),
+    Logs(Logs),
     Network(Network),
+    Power(Power),
     Storage(Storage),
+    Task(Task),
+    Session(Session),
 }
 
 #[derive(Debug, Args)]
-#[command(args_conflicts_with_subcommands = true)]
 struct Idrac {
     #[command(subcommand)]
-    command: Option<IdracCommands>,
+    command: IdracCommands,
 }
 
 #[derive(Debug, Subcommand)]
@@ -52,22 +115,312 @@ enum IdracCommands {
 }
 
 #[derive(Debug, Args)]
-#[command(args_conflicts_with_subcommands = true)]
+struct Bios {
+    #[command(subcommand)]
+    command: BiosCommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum BiosCommands {
+    /// List every BIOS attribute with its current and pending value
+    List,
+    /// List the attributes whose new value has not been applied yet
+    Pending,
+    /// Show one BIOS attribute with its description and allowed values
+    Get(BiosAttribute),
+    /// Set one or more BIOS attributes, applied at the next reboot unless told otherwise
+    Set(BiosSet),
+    /// Compare the BIOS attributes with another host or a baseline file, failing when they differ
+    Diff(BiosDiff),
+}
+
+#[derive(Debug, Args)]
+struct BiosAttribute {
+    #[arg(short, long)]
+    name: String,
+}
+
+#[derive(Debug, Args)]
+struct BiosSet {
+    /// Attributes to set, e.g. `SysProfile=PerfOptimized`
+    #[arg(required = true, value_parser = parse_assignment)]
+    attributes: Vec<(String, String)>,
+    /// When to apply the new values
+    #[arg(long, value_enum, default_value_t = ApplyTime::OnReset)]
+    apply: ApplyTime,
+    /// Start of the maintenance window, e.g. `2026-10-20T22:00:00-05:00`
+    #[arg(long)]
+    maintenance_start: Option<String>,
+    /// Length of the maintenance window in seconds
+    #[arg(long, default_value_t = 3600, requires = "maintenance_start")]
+    maintenance_duration: u64,
+    #[command(flatten)]
+    wait: JobWait,
+}
+
+impl BiosSet {
+    fn maintenance_window(&self) -> Option<MaintenanceWindow> {
+        self.maintenance_start.as_ref().map(|start| MaintenanceWindow {
+            start: start.to_owned(),
+            duration_in_seconds: self.maintenance_duration,
+        })
+    }
+}
+
+#[derive(Debug, Args)]
+#[command(group(ArgGroup::new("reference").required(true).args(["against", "baseline"])))]
+struct BiosDiff {
+    /// Profile of the host to compare with
+    #[arg(long)]
+    against: Option<String>,
+    /// JSON or YAML file with the expected attributes, e.g. saved with `bios list -o json`
+    #[arg(long)]
+    baseline: Option<PathBuf>,
+    /// Attribute to leave out of the comparison, e.g. `AssetTag`; may be repeated
+    #[arg(long)]
+    ignore: Vec<String>,
+}
+
+fn parse_assignment(arg: &str) -> Result<(String, String), String> {
+    match arg.split_once('=') {
+        Some((name, value)) if !name.is_empty() => Ok((name.to_owned(), value.to_owned())),
+        _ => Err(format!("expected NAME=VALUE, got '{}'", arg)),
+    }
+}
+
+#[derive(Debug, Args)]
+struct Boot {
+    #[command(subcommand)]
+    command: BootCommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum BootCommands {
+    /// Show the boot order, the boot mode and any pending one-time boot
+    Show,
+    /// Boot from a device on the next boot only, e.g. `pxe` to reprovision
+    Once(BootOnce),
+    /// Move boot options to the front of the persistent boot order
+    Order(BootOrder),
+}
+
+#[derive(Debug, Args)]
+struct BootOnce {
+    #[arg(value_enum)]
+    target: BootTarget,
+    /// Boot mode to use for that boot
+    #[arg(long, value_enum)]
+    mode: Option<BootMode>,
+}
+
+#[derive(Debug, Args)]
+struct BootOrder {
+    /// Boot option references or display names, first to boot first, e.g. `Boot0002`
+    #[arg(required = true)]
+    options: Vec<String>,
+    #[command(flatten)]
+    wait: JobWait,
+}
+
+#[derive(Debug, Args)]
 struct Chassis {
     #[command(subcommand)]
-    command: Option<ChassisCommands>,
+    command: ChassisCommands,
 }
 
 #[derive(Debug, Subcommand)]
 enum ChassisCommands {
-    Info
+    Info,
+    /// Turn the identify LED on or off, to find the server in the rack
+    Identify(Identify),
+}
+
+#[derive(Debug, Args)]
+#[command(group(ArgGroup::new("state").required(true).args(["on", "off", "blink"])))]
+struct Identify {
+    #[arg(long)]
+    on: bool,
+    #[arg(long)]
+    off: bool,
+    #[arg(long)]
+    blink: bool,
+    /// Seconds after which to turn the LED off again; the command keeps running until then
+    #[arg(short, long, conflicts_with = "off")]
+    duration: Option<u64>,
+}
+
+impl Identify {
+    fn indicator(&self) -> Indicator {
+        match (self.on, self.blink) {
+            (true, _) => Indicator::On,
+            (_, true) => Indicator::Blink,
+            _ => Indicator::Off,
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+struct Firmware {
+    #[command(subcommand)]
+    command: FirmwareCommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum FirmwareCommands {
+    /// List firmware with version, install state and release date
+    List(FirmwareList),
+    /// Install a Dell Update Package, or an image the iDRAC fetches from a URI, and follow the update
+    Update(FirmwareUpdate),
+    /// Compare installed firmware with a catalog, exiting with code 11 when components are out of date
+    Check(FirmwareCheck),
+}
+
+#[derive(Debug, Args)]
+struct FirmwareList {
+    /// Only show firmware for these kinds of components
+    #[arg(long = "type", value_enum, value_delimiter = ',')]
+    types: Vec<ComponentType>,
+}
+
+#[derive(Debug, Args)]
+struct FirmwareUpdate {
+    /// Local update package, or an HTTP, HTTPS, NFS or CIFS URI the iDRAC downloads it from
+    image: Image,
+    /// When to install the update
+    #[arg(long, value_enum, default_value_t = UpdateApplyTime::Immediate)]
+    apply: UpdateApplyTime,
+    /// Seconds to wait for the update before giving up
+    #[arg(long, default_value_t = 1800)]
+    timeout: u64,
+}
+
+#[derive(Debug, Args)]
+struct FirmwareCheck {
+    /// Dell catalog (Catalog.xml), or a JSON or YAML manifest of desired versions
+    #[arg(long)]
+    catalog: PathBuf,
+}
+
+#[derive(Debug, Args)]
+struct Thermal {
+    #[command(subcommand)]
+    command: ThermalCommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum ThermalCommands {
+    /// Show temperature sensors and fans, highlighting readings past their thresholds
+    Status
+}
+
+#[derive(Debug, Args)]
+struct Jobs {
+    #[command(subcommand)]
+    command: JobsCommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum JobsCommands {
+    /// List the jobs in the Lifecycle Controller job queue
+    List,
+    /// Show the state and progress of a job
+    Get(JobId),
+    /// Delete a job that is not running
+    Delete(JobId),
+    /// Delete every job in the queue
+    Clear,
+    /// Wait for a job to finish, failing if the job fails
+    Wait(JobIdWait),
+}
+
+#[derive(Debug, Args)]
+struct JobId {
+    /// Job id, e.g. `JID_878632532911`
+    id: String,
+}
+
+#[derive(Debug, Args)]
+struct JobIdWait {
+    /// Job id, e.g. `JID_878632532911`
+    id: String,
+    /// Seconds to wait for the job before giving up
+    #[arg(long, default_value_t = 1800)]
+    timeout: u64,
+}
+
+#[derive(Debug, Args)]
+struct JobWait {
+    /// Wait until the job the iDRAC creates for the change has finished, or is scheduled for the next reboot
+    #[arg(long)]
+    wait: bool,
+    /// Seconds to wait for the job before giving up
+    #[arg(long, default_value_t = 1800, requires = "wait")]
+    timeout: u64,
+}
+
+impl JobWait {
+    fn duration(&self) -> Option<Duration> {
+        self.wait.then(|| Duration::from_secs(self.timeout))
+    }
+}
+
+#[derive(Debug, Args)]
+struct Logs {
+    #[command(subcommand)]
+    command: LogsCommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum LogsCommands {
+    /// Show entries of the System Event Log or the Lifecycle log
+    List(LogsList),
+    /// Delete every entry of a log
+    Clear(LogName),
+}
+
+#[derive(Debug, Args)]
+struct LogsList {
+    #[arg(value_enum)]
+    log: Log,
+    /// Only show entries at least this severe
+    #[arg(long, value_enum)]
+    severity: Option<Level>,
+    /// Only show entries logged at or after this time, e.g. `2026-10-18`, `2026-10-18 08:30` or `12h` ago
+    #[arg(long, value_parser = parse_time)]
+    since: Option<DateTime<FixedOffset>>,
+    /// Only show entries logged at or before this time
+    #[arg(long, value_parser = parse_time)]
+    until: Option<DateTime<FixedOffset>>,
+    /// Only show entries with these message ids, e.g. `PSU0003` or `PSU*`
+    #[arg(long = "message-id", value_delimiter = ',')]
+    message_ids: Vec<String>,
+    /// Stop after this many entries
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+    limit: Option<u64>,
+}
+
+impl LogsList {
+    fn filter(&self) -> LogFilter {
+        LogFilter {
+            severity: self.severity,
+            since: self.since,
+            until: self.until,
+            message_ids: self.message_ids.clone(),
+            limit: self.limit.map(|limit| limit as usize),
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+struct LogName {
+    #[arg(value_enum)]
+    log: Log,
 }
 
 #[derive(Debug, Args)]
-#[command(args_conflicts_with_subcommands = true)]
 struct Network {
     #[command(subcommand)]
-    command: Option<NetworkCommands>,
+    command: NetworkCommands,
 }
 
 #[derive(Debug, Subcommand)]
@@ -80,7 +433,7 @@ enum NetworkCommands {
 #[derive(Debug, Args)]
 struct NetworkAdapter {
     #[arg(short, long)]
-    name: Option<String>,
+    name: String,
     #[arg(short, long)]
     detailed: bool,
 }
@@ -88,16 +441,87 @@ struct NetworkAdapter {
 #[derive(Debug, Args)]
 struct NetworkPort {
     #[arg(short, long)]
-    adapter: Option<String>,
+    adapter: String,
     #[arg(short, long)]
-    port: Option<String>,
+    port: String,
+}
+
+#[derive(Debug, Args)]
+struct Power {
+    #[command(subcommand)]
+    command: PowerCommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum PowerCommands {
+    /// Show power consumption, the power cap and the power supplies
+    Status,
+    /// Show, set or clear the chassis power cap
+    Cap(PowerCap),
+    /// List the reset types the system accepts
+    ListResetTypes,
+    /// Power the system on (On)
+    On(Wait),
+    /// Cut power immediately (ForceOff)
+    Off(Wait),
+    /// Ask the operating system to shut down (GracefulShutdown)
+    Shutdown(Wait),
+    /// Ask the operating system to restart (GracefulRestart)
+    Restart,
+    /// Restart without waiting for the operating system (ForceRestart)
+    ForceRestart,
+    /// Power off and on again (PowerCycle)
+    Cycle,
+    /// Send a non-maskable interrupt, e.g. to trigger a crash dump (Nmi)
+    Nmi,
+}
+
+#[derive(Debug, Args)]
+struct PowerCap {
+    #[command(subcommand)]
+    command: PowerCapCommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum PowerCapCommands {
+    /// Show the power cap
+    Show,
+    /// Limit the power the chassis may consume
+    Set(PowerCapLimit),
+    /// Remove the power limit
+    Clear,
+}
+
+#[derive(Debug, Args)]
+struct PowerCapLimit {
+    /// Limit in watts
+    #[arg(short, long)]
+    watts: u32,
+    /// What to do when consumption cannot be kept below the limit
+    #[arg(short, long, value_enum)]
+    exception: Option<LimitException>,
+}
+
+#[derive(Debug, Args)]
+struct Wait {
+    /// Wait until the system reaches the new power state
+    #[arg(long)]
+    wait: bool,
+    /// Seconds to wait for the new power state before giving up
+    #[arg(long, default_value_t = 300, requires = "wait")]
+    timeout: u64,
+}
+
+impl Wait {
+    fn duration(&self) -> Option<Duration> {
+        self.wait.then(|| Duration::from_secs(self.timeout))
+    }
 }
 
 #[derive(Debug, Args)]
-#[command(args_conflicts_with_subcommands = true)]
 struct Storage {
     #[command(subcommand)]
-    command: Option<StorageCommands>,
+    command: StorageCommands,
 }
 
 #[derive(Debug, Subcommand)]
@@ -112,78 +536,297 @@ enum StorageCommands {
 #[derive(Debug, Args)]
 struct StorageController {
     #[arg(short, long)]
-    name: Option<String>,
+    name: String,
 }
 
 #[derive(Debug, Args)]
 struct StorageVolumes {
     #[arg(short, long)]
-    controller: Option<String>,
+    controller: String,
 }
 
 #[derive(Debug, Args)]
 struct StorageVolume {
     #[arg(short, long)]
-    name: Option<String>,
+    name: String,
 }
 
 #[derive(Debug, Args)]
 struct StorageDisk {
     #[arg(short, long)]
-    name: Option<String>,
+    name: String,
 }
 
-#[derive(Debug, Deserialize)]
-pub struct Settings {
-    host: String,
-    user: String,
-    password: String
+#[derive(Debug, Args)]
+struct Task {
+    #[command(subcommand)]
+    command: TaskCommands,
 }
 
-fn make() -> Config {
-    Config::builder()
-        .add_source(config::File::with_name("./settings"))
-        .build()
-        .unwrap()
+#[derive(Debug, Subcommand)]
+enum TaskCommands {
+    /// Follow a task or task monitor until it finishes, failing if the task fails
+    Watch(TaskWatch),
 }
 
-fn main() {
+#[derive(Debug, Args)]
+struct TaskWatch {
+    /// Task or task monitor, e.g. the `Location` of a 202 response
+    uri: String,
+    /// Seconds to wait for the task before giving up
+    #[arg(long, default_value_t = 1800)]
+    timeout: u64,
+}
+
+#[derive(Debug, Args)]
+struct Session {
+    #[command(subcommand)]
+    command: SessionCommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum SessionCommands {
+    Logout
+}
+
+/// Reads the BIOS attributes of the host configured in `profile`, to compare another host with.
+async fn profile_baseline(opts: &Opts, profile: &str) -> Result<Baseline, Error> {
+    let mut settings = Settings::load(&Selection {
+        config: opts.config.clone(),
+        profile: Some(profile.to_owned()),
+        host: None,
+    })?;
+    settings.insecure |= opts.insecure;
+    let client = RedfishClient::new(&settings)?;
+
+    let baseline = Baseline::from_host(profile, &client).await;
+    let closed = client.close().await;
+    let baseline = baseline?;
+    closed.map(|_| baseline)
+}
+
+/// Waits for a job, drawing its progress on stderr.
+async fn wait_with_progress(id: &str, timeout: Duration, client: &RedfishClient) -> Result<Job, Error> {
+    let mut progress = Progress::new();
+    wait_for_job(id, timeout, |job| {
+        progress.update(job.percent_complete, &format!("{}: {}", or_na(&job.job_state), or_na(&job.message)))
+    }, client).await
+}
+
+/// Waits for a task, drawing its progress on stderr.
+async fn watch_with_progress(uri: &str, timeout: Duration, client: &RedfishClient) -> Result<task::Task, Error> {
+    let mut progress = Progress::new();
+    client.wait_for_task(uri, timeout, |task| {
+        progress.update(task.percent_complete, &format!("{}: {}", or_na(&task.task_state), or_na(&task.last_message())))
+    }).await
+}
+
+/// Installs firmware, drawing the progress of the update on stderr.
+async fn update_with_progress(update: &FirmwareUpdate, client: &RedfishClient) -> Result<impl Render, Error> {
+    let mut progress = Progress::new();
+    update_firmware(&update.image, update.apply, Duration::from_secs(update.timeout), |task| {
+        progress.update(task.percent_complete, &format!("{}: {}", or_na(&task.task_state), or_na(&task.last_message())))
+    }, client).await
+}
+
+/// Waits for the job the iDRAC created to apply a change, if it created one, drawing its progress
+/// on stderr. Returns the state the job reached; with `until_scheduled`, that is as soon as it is
+/// scheduled, since such jobs only run on the next reboot or in a maintenance window.
+async fn wait_for_change_job(job: Option<&str>, until_scheduled: bool, timeout: Duration, client: &RedfishClient) -> Result<Option<String>, Error> {
+    let Some(id) = job else {
+        return Ok(None);
+    };
+    let mut progress = Progress::new();
+    let update = |job: &Job| progress.update(job.percent_complete, &format!("{}: {}", or_na(&job.job_state), or_na(&job.message)));
+    let job = match until_scheduled {
+        true => wait_for_job_or_pending(id, timeout, update, client).await?,
+        false => wait_for_job(id, timeout, update, client).await?,
+    };
+    Ok(job.job_state)
+}
+
+fn show<T: Render>(result: Result<T, Error>, format: Format) -> Result<(), Error> {
+    output::print(&result?, format);
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() {
     let opts: Opts = Opts::parse();
-    let c = make();
-    let settings: Settings = c.try_deserialize().unwrap();
 
-    match &opts.command {
+    if let Err(err) = run(&opts).await {
+        eprintln!("Error: {}", err);
+        process::exit(err.exit_code());
+    }
+}
+
+async fn run(opts: &Opts) -> Result<(), Error> {
+    let selection = Selection {
+        config: opts.config.clone(),
+        profile: opts.profile.clone(),
+        host: opts.host.clone(),
+    };
+    // Logging out only needs the session token, so there is no point in asking for a password.
+    let mut settings = match opts.command {
+        Commands::Session(_) => Settings::load_without_password(&selection)?,
+        _ => Settings::load(&selection)?,
+    };
+    settings.insecure |= opts.insecure;
+    settings.system = opts.system.clone().or(settings.system);
+    settings.manager = opts.manager.clone().or(settings.manager);
+    let client = RedfishClient::new(&settings)?;
+
+    let result = match &opts.command {
         Commands::Idrac(idrac) => {
-            match idrac.command.as_ref().unwrap() {
-                IdracCommands::Version => get_idrac_version(settings).expect("Panic!")
+            match &idrac.command {
+                IdracCommands::Version => show(get_idrac_version(&client).await, opts.output)
+            }
+        }
+        Commands::Bios(bios) => {
+            match &bios.command {
+                BiosCommands::List => show(list_bios_attributes(&client).await, opts.output),
+                BiosCommands::Pending => show(list_pending_bios_attributes(&client).await, opts.output),
+                BiosCommands::Get(attribute) => show(get_bios_attribute(&attribute.name, &client).await, opts.output),
+                BiosCommands::Set(set) => {
+                    let result = set_bios_attributes(&set.attributes, set.apply, set.maintenance_window(), &client).await;
+                    let result = match (result, set.wait.duration()) {
+                        (Ok(mut changes), Some(timeout)) => {
+                            let until_scheduled = set.apply != ApplyTime::Immediate;
+                            wait_for_change_job(changes.job.as_deref(), until_scheduled, timeout, &client).await
+                                .map(|state| { changes.job_state = state; changes })
+                        }
+                        (result, _) => result,
+                    };
+                    show(result, opts.output)
+                }
+                BiosCommands::Diff(diff) => {
+                    let baseline = match (&diff.against, &diff.baseline) {
+                        (Some(profile), _) => profile_baseline(opts, profile).await,
+                        (None, Some(file)) => Baseline::read(file),
+                        (None, None) => unreachable!("clap requires --against or --baseline"),
+                    };
+                    let result = match baseline {
+                        Ok(baseline) => diff_bios_attributes(&baseline, &diff.ignore, &client).await,
+                        Err(err) => Err(err),
+                    };
+                    result.and_then(|result| {
+                        output::print(&result, opts.output);
+                        result.check()
+                    })
+                }
+            }
+        }
+        Commands::Boot(boot) => {
+            match &boot.command {
+                BootCommands::Show => show(get_boot_settings(&client).await, opts.output),
+                BootCommands::Once(once) => show(set_boot_override(once.target, once.mode, &client).await, opts.output),
+                BootCommands::Order(order) => {
+                    let result = set_boot_order(&order.options, &client).await;
+                    let result = match (result, order.wait.duration()) {
+                        (Ok(mut settings), Some(timeout)) => {
+                            wait_for_change_job(settings.job.as_deref(), true, timeout, &client).await
+                                .map(|state| { settings.job_state = state; settings })
+                        }
+                        (result, _) => result,
+                    };
+                    show(result, opts.output)
+                }
             }
         }
         Commands::Chassis(chassis) => {
-            match chassis.command.as_ref().unwrap() {
-                ChassisCommands::Info => get_chassis_info(settings).expect("Panic!"),
+            match &chassis.command {
+                ChassisCommands::Info => show(get_chassis_info(&client).await, opts.output),
+                ChassisCommands::Identify(identify) => {
+                    let duration = identify.duration.map(Duration::from_secs);
+                    if let Some(seconds) = identify.duration {
+                        eprintln!("Turning the indicator off again in {} seconds, keep this command running until then", seconds);
+                    }
+                    show(set_indicator(identify.indicator(), duration, &client).await, opts.output)
+                }
+            }
+        }
+        Commands::Firmware(firmware) => {
+            match &firmware.command {
+                FirmwareCommands::List(list) => show(list_firmware(&list.types, &client).await, opts.output),
+                FirmwareCommands::Update(update) => show(update_with_progress(update, &client).await, opts.output),
+                FirmwareCommands::Check(check) => {
+                    let result = match Catalog::read(&check.catalog) {
+                        Ok(catalog) => check_firmware(&catalog, &client).await,
+                        Err(err) => Err(err),
+                    };
+                    result.and_then(|result| {
+                        output::print(&result, opts.output);
+                        result.check()
+                    })
+                }
+            }
+        }
+        Commands::Thermal(thermal) => {
+            match &thermal.command {
+                ThermalCommands::Status => show(get_thermal_info(&client).await, opts.output),
+            }
+        }
+        Commands::Jobs(jobs) => {
+            match &jobs.command {
+                JobsCommands::List => show(list_jobs(&client).await, opts.output),
+                JobsCommands::Get(job) => show(get_job(&job.id, &client).await, opts.output),
+                JobsCommands::Delete(job) => show(delete_job(&job.id, &client).await, opts.output),
+                JobsCommands::Clear => show(clear_jobs(&client).await, opts.output),
+                JobsCommands::Wait(job) => show(wait_with_progress(&job.id, Duration::from_secs(job.timeout), &client).await, opts.output),
+            }
+        }
+        Commands::Logs(logs) => {
+            match &logs.command {
+                LogsCommands::List(list) => show(list_log_entries(list.log, &list.filter(), &client).await, opts.output),
+                LogsCommands::Clear(clear) => show(clear_log(clear.log, &client).await, opts.output),
             }
         }
         Commands::Network(network) => {
-            match network.command.as_ref().unwrap() {
-                NetworkCommands::ListNetworkAdapters => list_network_adapters(settings).expect("Panic!"),
-                NetworkCommands::GetNetworkAdapter(network_adapter) => {
-                    if network_adapter.detailed {
-                        get_network_adapter(&network_adapter.name, settings, true).expect("Panic!")
-                    } else {
-                        get_network_adapter(&network_adapter.name, settings, false).expect("Panic!")
-                    }
+            match &network.command {
+                NetworkCommands::ListNetworkAdapters => show(list_network_adapters(&client).await, opts.output),
+                NetworkCommands::GetNetworkAdapter(network_adapter) => show(get_network_adapter(&network_adapter.name, &client, network_adapter.detailed).await, opts.output),
+                NetworkCommands::GetNetworkPort(network_port) => show(get_network_port(&network_port.adapter, &network_port.port, &client).await, opts.output),
+            }
+        }
+        Commands::Power(power) => {
+            match &power.command {
+                PowerCommands::Status => show(get_power_status(&client).await, opts.output),
+                PowerCommands::Cap(cap) => match &cap.command {
+                    PowerCapCommands::Show => show(get_power_cap(&client).await, opts.output),
+                    PowerCapCommands::Set(limit) => show(set_power_cap(Some(limit.watts), limit.exception, &client).await, opts.output),
+                    PowerCapCommands::Clear => show(clear_power_cap(&client).await, opts.output),
                 },
-                NetworkCommands::GetNetworkPort(network_port) => get_network_port(network_port.adapter.as_ref().unwrap(), network_port.port.as_ref().unwrap(), &settings),
+                PowerCommands::ListResetTypes => show(list_reset_types(&client).await, opts.output),
+                PowerCommands::On(wait) => show(reset_system(ResetType::On, wait.duration(), &client).await, opts.output),
+                PowerCommands::Off(wait) => show(reset_system(ResetType::ForceOff, wait.duration(), &client).await, opts.output),
+                PowerCommands::Shutdown(wait) => show(reset_system(ResetType::GracefulShutdown, wait.duration(), &client).await, opts.output),
+                PowerCommands::Restart => show(reset_system(ResetType::GracefulRestart, None, &client).await, opts.output),
+                PowerCommands::ForceRestart => show(reset_system(ResetType::ForceRestart, None, &client).await, opts.output),
+                PowerCommands::Cycle => show(reset_system(ResetType::PowerCycle, None, &client).await, opts.output),
+                PowerCommands::Nmi => show(reset_system(ResetType::Nmi, None, &client).await, opts.output),
             }
         }
         Commands::Storage(storage) => {
-            match storage.command.as_ref().unwrap() {
-                StorageCommands::ListStorageControllers => list_storage_controllers(settings).expect("Panic!"),
-                StorageCommands::GetStorageController(storage_controller) => get_storage_controller(&storage_controller.name, settings).expect("Panic!"),
-                StorageCommands::ListStorageVolumes(storage_volume) => list_storage_volumes(&storage_volume.controller, settings).expect("Panic!"),
-                StorageCommands::GetStorageVolume(storage_volume) => get_storage_volume(&storage_volume.name, settings).expect("Panic!"),
-                StorageCommands::GetStorageDisk(storage_disk) => get_storage_disk(&storage_disk.name, settings).expect("Panic!"),
+            match &storage.command {
+                StorageCommands::ListStorageControllers => show(list_storage_controllers(&client).await, opts.output),
+                StorageCommands::GetStorageController(storage_controller) => show(get_storage_controller(&storage_controller.name, &client).await, opts.output),
+                StorageCommands::ListStorageVolumes(storage_volume) => show(list_storage_volumes(&storage_volume.controller, &client).await, opts.output),
+                StorageCommands::GetStorageVolume(storage_volume) => show(get_storage_volume(&storage_volume.name, &client).await, opts.output),
+                StorageCommands::GetStorageDisk(storage_disk) => show(get_storage_disk(&storage_disk.name, &client).await, opts.output),
             }
         }
-    }
-}
\ No newline at end of file
+        Commands::Task(task) => {
+            match &task.command {
+                TaskCommands::Watch(watch) => show(watch_with_progress(&watch.uri, Duration::from_secs(watch.timeout), &client).await, opts.output),
+            }
+        }
+        Commands::Session(session) => {
+            match &session.command {
+                SessionCommands::Logout => show(logout(&client).await, opts.output),
+            }
+        }
+    };
+
+    let closed = client.close().await;
+    result.and(closed)
+}
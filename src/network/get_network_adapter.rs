@@ -1,110 +1,127 @@
-use std::time::Duration;
-use reqwest::{Client, Error};
-use reqwest;
+use crate::error::Error;
 use serde::{Serialize, Deserialize};
+use serde_json::Value;
+use crate::client::RedfishClient;
+use crate::output::{or_na, short_name, Render};
 
-use crate::network::get_network_port::NetworkAdapterList;
-use crate::Settings;
+use crate::network::get_network_port::{get_network_port, NetworkAdapterList};
 
 #[derive(Debug, Serialize, Deserialize)]
-struct NetworkAdapterInfo {
+pub struct NetworkAdapterInfo {
     #[serde(rename="Manufacturer")]
-    manufacturer: String,
+    pub manufacturer: Option<String>,
     #[serde(rename="Model")]
-    model: String,
+    pub model: Option<String>,
     #[serde(rename="PartNumber")]
-    part_number: String,
+    pub part_number: Option<String>,
     #[serde(rename="SerialNumber")]
-    serial_number: String,
-    #[serde(rename="Controllers")]
-    controllers: Vec<NetworkAdapterController>
+    pub serial_number: Option<String>,
+    #[serde(rename="Controllers", default)]
+    pub controllers: Vec<NetworkAdapterController>,
+    #[serde(rename="Oem", skip_serializing_if="Option::is_none")]
+    pub oem: Option<Value>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
-struct NetworkAdapterController {
+pub struct NetworkAdapterController {
     #[serde(rename="FirmwarePackageVersion")]
-    firmware_package_version: String,
-    #[serde(rename="Links")]
-    links:NetworkAdapterControllerLink
+    pub firmware_package_version: Option<String>,
+    #[serde(rename="Links", default)]
+    pub links: NetworkAdapterControllerLink
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct NetworkAdapterControllerLink {
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct NetworkAdapterControllerLink {
     #[serde(rename="NetworkPorts@odata.count")]
-    port_count: u8,
-    #[serde(rename="NetworkPorts")]
-    network_ports: Vec<NetworkAdapterControllerPort>
+    pub port_count: Option<u32>,
+    #[serde(rename="NetworkPorts", default)]
+    pub network_ports: Vec<NetworkAdapterControllerPort>
 }
 
 #[derive(Debug, Serialize, Deserialize)]
-struct NetworkAdapterControllerPort {
+pub struct NetworkAdapterControllerPort {
     #[serde(rename="@odata.id")]
-    name: String
+    pub name: String
 }
 
-#[tokio::main]
-pub async fn get_network_adapter(network_adapter: &Option<String>, settings: Settings, detailed: bool) -> Result<(), Error> {
-    let response = Client::builder()
-        .danger_accept_invalid_certs(true)
-        .timeout(Duration::from_secs(30))
-        .build()
-        .unwrap()
-        .get(format!("https://{}/redfish/v1/Systems/System.Embedded.1/NetworkAdapters/{}", settings.host.to_owned(), network_adapter.as_ref().unwrap()))
-        .basic_auth(&settings.user, Some(&settings.password))
-        .send()
-        .await
-        .unwrap();
-    let response_json: NetworkAdapterInfo = match response.json().await {
-        Ok(r) => r,
-        Err(e) => panic!("Could not introspect the token. Error was:\n {:?}", e),
-    };
+/// A network adapter with its ports, and the port details when asked for `--detailed`.
+#[derive(Debug, Serialize)]
+pub struct NetworkAdapter {
+    #[serde(rename="Id")]
+    pub id: String,
+    #[serde(flatten)]
+    pub info: NetworkAdapterInfo,
+    #[serde(rename="Ports")]
+    pub ports: Vec<NetworkAdapterPort>,
+}
 
-    println!("NIC:           {}", network_adapter.as_ref().unwrap());
-    println!("Manufacturer:  {}", response_json.manufacturer);
-    println!("Model:         {}", response_json.model);
-    println!("Part number:   {}", response_json.part_number);
-    println!("Serial number: {}\n", response_json.serial_number);
+#[derive(Debug, Serialize)]
+pub struct NetworkAdapterPort {
+    #[serde(rename="Id")]
+    pub id: String,
+    #[serde(flatten, skip_serializing_if="Option::is_none")]
+    pub details: Option<NetworkAdapterList>,
+}
 
-    let mut port_list: Vec<String> = Vec::new();
+impl Render for NetworkAdapter {
+    fn columns(&self) -> Vec<&'static str> {
+        let mut columns = vec!["Adapter", "Manufacturer", "Model", "PartNumber", "SerialNumber", "Port"];
+        columns.extend(NetworkAdapterList::columns());
+        columns
+    }
 
-    for controller in response_json.controllers.iter(){
-        println!("Found {} ports on controller:", controller.links.port_count);
+    fn rows(&self) -> Vec<Vec<String>> {
+        self.ports.iter().map(|port| {
+            let mut row = vec![
+                self.id.to_owned(),
+                or_na(&self.info.manufacturer),
+                or_na(&self.info.model),
+                or_na(&self.info.part_number),
+                or_na(&self.info.serial_number),
+                port.id.to_owned(),
+            ];
+            match &port.details {
+                Some(details) => row.extend(details.row()),
+                None => row.extend(NetworkAdapterList::columns().iter().map(|_| String::new())),
+            }
+            row
+        }).collect()
+    }
 
-        for link in &controller.links.network_ports {
-            let long_name = &link.name;
-            let short_name = long_name.replace(&format!("/redfish/v1/Systems/System.Embedded.1/NetworkAdapters/{}/NetworkPorts/", network_adapter.as_ref().unwrap()), "");
+    fn human(&self) -> String {
+        let mut out = String::new();
+        out += &format!("NIC:           {}\n", self.id);
+        out += &format!("Manufacturer:  {}\n", or_na(&self.info.manufacturer));
+        out += &format!("Model:         {}\n", or_na(&self.info.model));
+        out += &format!("Part number:   {}\n", or_na(&self.info.part_number));
+        out += &format!("Serial number: {}\n\n", or_na(&self.info.serial_number));
 
-            port_list.push(short_name);
+        for controller in &self.info.controllers {
+            out += &format!("Found {} ports on controller:\n", controller.links.port_count.unwrap_or(controller.links.network_ports.len() as u32));
+        }
+        for port in &self.ports {
+            out += &format!("- {}\n", port.id);
+            if let Some(details) = &port.details {
+                out += &details.details("  ");
+            }
         }
+        out
     }
+}
 
-    if detailed {
-        for port in port_list {
-            println!("- {}", port);
-
-            let response = Client::builder()
-                .danger_accept_invalid_certs(true)
-                .timeout(Duration::from_secs(30))
-                .build()
-                .unwrap()
-                .get(format!("https://{}/redfish/v1/Systems/System.Embedded.1/NetworkAdapters/{}/NetworkPorts/{}", settings.host.to_owned(), network_adapter.as_ref().unwrap(), port))
-                .basic_auth(&settings.user, Some(&settings.password))
-                .send()
-                .await
-                .unwrap();
-
-            let response_json: NetworkAdapterList = match response.json().await {
-                Ok(r) => r,
-                Err(e) => panic!("Could not introspect the token. Error was:\n {:?}", e),
-            };
-            println!("{:?}", response_json)
+pub async fn get_network_adapter(network_adapter: &str, client: &RedfishClient, detailed: bool) -> Result<NetworkAdapter, Error> {
+    let response_json: NetworkAdapterInfo = client.get(&format!("{}/{}", client.network_adapters().await?, network_adapter))
+        .await
+        .map_err(|e| e.not_found_as(format!("network adapter '{}'", network_adapter)))?;
 
-        }
-    } else {
-        for port in port_list {
-            println!("- {}", port);
+    let mut ports = Vec::new();
+    for controller in &response_json.controllers {
+        for link in &controller.links.network_ports {
+            let id = short_name(&link.name);
+            let details = if detailed { Some(get_network_port(network_adapter, &id, client).await?) } else { None };
+            ports.push(NetworkAdapterPort { id, details });
         }
     }
 
-    Ok(())
-}
\ No newline at end of file
+    Ok(NetworkAdapter { id: network_adapter.to_owned(), info: response_json, ports })
+}
@@ -1,60 +1,87 @@
-use std::time::Duration;
-use reqwest::{Client, Error};
-use reqwest;
+use crate::error::Error;
 use serde::{Serialize, Deserialize};
-use crate::Settings;
+use serde_json::Value;
+use crate::client::RedfishClient;
+use crate::output::{or_na, Render};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct NetworkAdapterList {
     #[serde(rename="ActiveLinkTechnology")]
-    technology: String,
-    #[serde(rename="AssociatedNetworkAddresses")]
-    addresses: Vec<String>,
+    pub technology: Option<String>,
+    #[serde(rename="AssociatedNetworkAddresses", default)]
+    pub addresses: Vec<String>,
     #[serde(rename="LinkStatus")]
-    link_status: String,
+    pub link_status: Option<String>,
     #[serde(rename="PhysicalPortNumber")]
-    physical_port_number: String,
-    #[serde(rename="SupportedEthernetCapabilities")]
-    supported_ethernet_capabilities: Vec<String>,
-    #[serde(rename="SupportedLinkCapabilities")]
-    supported_link_capabilities: Vec<LinkCapability>,
+    pub physical_port_number: Option<String>,
+    #[serde(rename="SupportedEthernetCapabilities", default)]
+    pub supported_ethernet_capabilities: Vec<String>,
+    #[serde(rename="SupportedLinkCapabilities", default)]
+    pub supported_link_capabilities: Vec<LinkCapability>,
     #[serde(rename="WakeOnLANEnabled")]
-    wol_enabled: bool
+    pub wol_enabled: Option<bool>,
+    #[serde(rename="Oem", skip_serializing_if="Option::is_none")]
+    pub oem: Option<Value>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
-struct LinkCapability {
+pub struct LinkCapability {
     #[serde(rename="LinkNetworkTechnology")]
-    technology: String,
+    pub technology: Option<String>,
     #[serde(rename="LinkSpeedMbps")]
-    speed_mbps: u64,
+    pub speed_mbps: Option<u64>,
 }
 
-#[tokio::main]
-pub async fn retrieve_port_info(network_adapter: &String, port: &String, settings: &Settings) -> Result<NetworkAdapterList, Error> {
-    let response = Client::builder()
-        .danger_accept_invalid_certs(true)
-        .timeout(Duration::from_secs(30))
-        .build()
-        .unwrap()
-        .get(format!("https://{}/redfish/v1/Systems/System.Embedded.1/NetworkAdapters/{}/NetworkPorts/{}", settings.host.to_owned(), network_adapter, port))
-        .basic_auth(&settings.user, Some(&settings.password))
-        .send()
-        .await
-        .unwrap();
+impl NetworkAdapterList {
+    pub fn columns() -> Vec<&'static str> {
+        vec!["PhysicalPortNumber", "LinkStatus", "ActiveLinkTechnology", "AssociatedNetworkAddresses", "LinkSpeedsMbps", "WakeOnLANEnabled"]
+    }
 
-    let response_json: NetworkAdapterList = match response.json().await {
-        Ok(r) => r,
-        Err(e) => panic!("Could not introspect the token. Error was:\n {:?}", e),
-    };
+    pub fn row(&self) -> Vec<String> {
+        let speeds: Vec<String> = self.supported_link_capabilities.iter().map(|c| or_na(&c.speed_mbps)).collect();
+        vec![
+            or_na(&self.physical_port_number),
+            or_na(&self.link_status),
+            or_na(&self.technology),
+            self.addresses.join(" "),
+            speeds.join(" "),
+            or_na(&self.wol_enabled),
+        ]
+    }
 
-    // println!("{:?}", response_json);
-    Ok(response_json)
+    /// Key/value listing, indented by `indent` so it can be nested under an adapter.
+    pub fn details(&self, indent: &str) -> String {
+        let mut out = String::new();
+        out += &format!("{}Port number:    {}\n", indent, or_na(&self.physical_port_number));
+        out += &format!("{}Link status:    {}\n", indent, or_na(&self.link_status));
+        out += &format!("{}Technology:     {}\n", indent, or_na(&self.technology));
+        out += &format!("{}Addresses:      {}\n", indent, self.addresses.join(", "));
+        for capability in &self.supported_link_capabilities {
+            let speed = capability.speed_mbps.map(|s| format!("{} Mbps", s));
+            out += &format!("{}Link speed:     {} ({})\n", indent, or_na(&speed), or_na(&capability.technology));
+        }
+        out += &format!("{}Capabilities:   {}\n", indent, self.supported_ethernet_capabilities.join(", "));
+        out += &format!("{}Wake on LAN:    {}\n", indent, or_na(&self.wol_enabled));
+        out
+    }
 }
 
-pub fn get_network_port(network_adapter: &String, port: &String, settings: &Settings) {
-    match retrieve_port_info(network_adapter, port, settings) {
-        Ok(response) => println!("{:?}", response),
-        Err(err) => println!("Error: {}", err),
+impl Render for NetworkAdapterList {
+    fn columns(&self) -> Vec<&'static str> {
+        NetworkAdapterList::columns()
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        vec![self.row()]
     }
-}
\ No newline at end of file
+
+    fn human(&self) -> String {
+        self.details("")
+    }
+}
+
+pub async fn get_network_port(network_adapter: &str, port: &str, client: &RedfishClient) -> Result<NetworkAdapterList, Error> {
+    client.get(&format!("{}/{}/NetworkPorts/{}", client.network_adapters().await?, network_adapter, port))
+        .await
+        .map_err(|e| e.not_found_as(format!("port '{}' on network adapter '{}'", port, network_adapter)))
+}
@@ -0,0 +1,235 @@
+use std::env;
+use std::fmt::{self, Display};
+use std::io::{self, IsTerminal};
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::Value;
+
+/// Shown in place of properties the service did not report, or reported as null.
+pub const NOT_AVAILABLE: &str = "n/a";
+
+/// Output format selected with `--output`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Human readable text and tables
+    #[default]
+    Table,
+    Json,
+    Yaml,
+    Csv,
+}
+
+/// Implemented by every model a command returns, so it can be printed in any [`Format`].
+///
+/// JSON and YAML come straight from `Serialize`. CSV and the default table layout are
+/// built from `columns`/`rows`; single resources usually override `human` with a
+/// key/value listing instead.
+pub trait Render: Serialize {
+    fn columns(&self) -> Vec<&'static str>;
+
+    fn rows(&self) -> Vec<Vec<String>>;
+
+    fn human(&self) -> String {
+        table(&self.columns(), &self.rows())
+    }
+}
+
+/// Renders `value` in the requested format.
+pub fn render<T: Render>(value: &T, format: Format) -> String {
+    match format {
+        Format::Table => value.human(),
+        Format::Json => serde_json::to_string_pretty(value).expect("models always serialize to JSON") + "\n",
+        Format::Yaml => serde_yaml::to_string(value).expect("models always serialize to YAML"),
+        Format::Csv => csv(&value.columns(), &value.rows()),
+    }
+}
+
+/// Prints `value` to stdout in the requested format.
+pub fn print<T: Render>(value: &T, format: Format) {
+    print!("{}", render(value, format));
+}
+
+/// Lays out rows as a table with aligned columns.
+pub fn table(columns: &[&str], rows: &[Vec<String>]) -> String {
+    let mut widths: Vec<usize> = columns.iter().map(|c| c.chars().count()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let line = |cells: Vec<&str>| {
+        let padded: Vec<String> = cells.iter().zip(&widths).map(|(cell, width)| format!("{:<1$}", cell, width)).collect();
+        padded.join("  ").trim_end().to_owned() + "\n"
+    };
+    let mut out = line(columns.to_vec());
+    for row in rows {
+        out += &line(row.iter().map(String::as_str).collect());
+    }
+    out
+}
+
+/// Writes rows as RFC 4180 CSV with a header line.
+pub fn csv(columns: &[&str], rows: &[Vec<String>]) -> String {
+    let escape = |cell: &str| {
+        if cell.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", cell.replace('"', "\"\""))
+        } else {
+            cell.to_owned()
+        }
+    };
+    let mut out = columns.iter().map(|c| escape(c)).collect::<Vec<_>>().join(",") + "\n";
+    for row in rows {
+        out += &(row.iter().map(|c| escape(c)).collect::<Vec<_>>().join(",") + "\n");
+    }
+    out
+}
+
+/// Formats an optional property, falling back to [`NOT_AVAILABLE`].
+pub fn or_na<T: Display>(value: &Option<T>) -> String {
+    match value {
+        Some(value) => value.to_string(),
+        None => NOT_AVAILABLE.to_owned(),
+    }
+}
+
+/// Severity of a reading, used to highlight it on a terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, ValueEnum)]
+pub enum Level {
+    Ok,
+    Warning,
+    Critical,
+}
+
+impl Level {
+    /// Level of a Redfish `Status.Health` value.
+    pub fn of_health(health: Option<&str>) -> Level {
+        match health {
+            Some("Warning") => Level::Warning,
+            Some("Critical") => Level::Critical,
+            _ => Level::Ok,
+        }
+    }
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Level::Ok => "OK",
+            Level::Warning => "Warning",
+            Level::Critical => "Critical",
+        })
+    }
+}
+
+/// Colors `text` yellow or red for warnings and critical readings, when stdout is a terminal
+/// and `NO_COLOR` is not set.
+pub fn highlight(text: &str, level: Level) -> String {
+    if level == Level::Ok || env::var_os("NO_COLOR").is_some() || !io::stdout().is_terminal() {
+        return text.to_owned();
+    }
+    let color = if level == Level::Critical { "31" } else { "33" };
+    format!("\x1b[{}m{}\x1b[0m", color, text)
+}
+
+/// Lays out rows as a table like [`table`], highlighting each row with its level.
+pub fn highlighted_table(columns: &[&str], rows: &[Vec<String>], levels: &[Level]) -> String {
+    let plain = table(columns, rows);
+    let mut lines = plain.lines();
+    let mut out = lines.next().map(|header| header.to_owned() + "\n").unwrap_or_default();
+    for (line, level) in lines.zip(levels) {
+        out += &(highlight(line, *level) + "\n");
+    }
+    out
+}
+
+/// Progress of a long-running operation, written to stderr so it does not mix with the output:
+/// a bar redrawn in place on a terminal, otherwise a line whenever something changes, so logs
+/// of scripted runs stay readable.
+pub struct Progress {
+    terminal: bool,
+    last: Option<(Option<u32>, String)>,
+}
+
+impl Progress {
+    const WIDTH: usize = 30;
+
+    pub fn new() -> Progress {
+        Progress { terminal: io::stderr().is_terminal(), last: None }
+    }
+
+    pub fn update(&mut self, percent: Option<u32>, status: &str) {
+        let current = (percent, status.to_owned());
+        if self.last.as_ref() == Some(&current) {
+            return;
+        }
+        match (self.terminal, percent.map(|p| p.min(100))) {
+            (true, percent) => {
+                let done = percent.unwrap_or(0) as usize * Self::WIDTH / 100;
+                let shown = percent.map(|p| format!("{:>3}%", p)).unwrap_or_else(|| String::from("   ?"));
+                eprint!("\r\x1b[K[{}{}] {} {}", "#".repeat(done), "-".repeat(Self::WIDTH - done), shown, status);
+            }
+            (false, Some(percent)) => eprintln!("{}% {}", percent, status),
+            (false, None) => eprintln!("{}", status),
+        }
+        self.last = Some(current);
+    }
+}
+
+impl Default for Progress {
+    fn default() -> Progress {
+        Progress::new()
+    }
+}
+
+impl Drop for Progress {
+    /// Ends the bar's line, so whatever comes next starts on a fresh one.
+    fn drop(&mut self) {
+        if self.terminal && self.last.is_some() {
+            eprintln!();
+        }
+    }
+}
+
+/// Formats a free-form JSON property, e.g. a BIOS attribute: strings without quotes, null as [`NOT_AVAILABLE`].
+pub fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.to_owned(),
+        Value::Null => NOT_AVAILABLE.to_owned(),
+        other => other.to_string(),
+    }
+}
+
+/// Turns a Redfish resource link into its last path segment, e.g.
+/// `/redfish/v1/Systems/System.Embedded.1/Storage/RAID.Integrated.1-1` into `RAID.Integrated.1-1`.
+pub fn short_name(link: &str) -> String {
+    link.trim_end_matches('/').rsplit('/').next().unwrap_or(link).to_owned()
+}
+
+/// A list of resource ids, e.g. the network adapters of a system.
+#[derive(Debug, Serialize)]
+#[serde(transparent)]
+pub struct IdList {
+    /// What the ids are, used in the human readable header (`Found 2 network adapter(s):`).
+    #[serde(skip)]
+    pub kind: &'static str,
+    pub ids: Vec<String>,
+}
+
+impl Render for IdList {
+    fn columns(&self) -> Vec<&'static str> {
+        vec!["Id"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        self.ids.iter().map(|id| vec![id.to_owned()]).collect()
+    }
+
+    fn human(&self) -> String {
+        let mut out = format!("Found {} {}(s):\n", self.ids.len(), self.kind);
+        for id in &self.ids {
+            out += &format!("- {}\n", id);
+        }
+        out
+    }
+}
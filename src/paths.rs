@@ -0,0 +1,37 @@
+use std::env;
+use std::path::PathBuf;
+
+/// Directory holding the settings file: `$XDG_CONFIG_HOME/idrac-cli`, falling back to `~/.config/idrac-cli`.
+pub fn config_dir() -> Option<PathBuf> {
+    xdg_dir("XDG_CONFIG_HOME", ".config")
+}
+
+/// Directory used for state that may be thrown away at any time, such as cached sessions.
+///
+/// Follows the XDG base directory spec: `$XDG_CACHE_HOME/idrac-cli`, falling back to
+/// `~/.cache/idrac-cli`.
+pub fn cache_dir() -> Option<PathBuf> {
+    xdg_dir("XDG_CACHE_HOME", ".cache")
+}
+
+/// Directory used for state worth keeping, such as the fingerprints recorded on first use.
+///
+/// `$XDG_DATA_HOME/idrac-cli`, falling back to `~/.local/share/idrac-cli`.
+pub fn data_dir() -> Option<PathBuf> {
+    xdg_dir("XDG_DATA_HOME", ".local/share")
+}
+
+fn xdg_dir(variable: &str, fallback: &str) -> Option<PathBuf> {
+    let base = match env::var_os(variable) {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(env::var_os("HOME")?).join(fallback),
+    };
+    Some(base.join("idrac-cli"))
+}
+
+/// Turns a host name into something safe to use as a file name.
+pub fn file_name_for(host: &str) -> String {
+    host.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '.' || c == '-' || c == '_' || c == '@' { c } else { '_' })
+        .collect()
+}
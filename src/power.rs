@@ -0,0 +1,5 @@
+pub mod get_power_cap;
+pub mod get_power_status;
+pub mod list_reset_types;
+pub mod reset_system;
+pub mod set_power_cap;
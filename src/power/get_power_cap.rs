@@ -0,0 +1,75 @@
+use crate::error::Error;
+use serde::Serialize;
+use crate::client::RedfishClient;
+use crate::output::{or_na, Render};
+use crate::power::get_power_status::{PowerControl, PowerStatus};
+
+/// Power cap of the chassis, as set on its first power control.
+#[derive(Debug, Serialize)]
+pub struct PowerCap {
+    #[serde(rename="LimitInWatts")]
+    pub limit_in_watts: Option<f64>,
+    #[serde(rename="LimitException")]
+    pub limit_exception: Option<String>,
+    #[serde(rename="CorrectionInMs")]
+    pub correction_in_ms: Option<u64>,
+    #[serde(rename="PowerConsumedWatts")]
+    pub power_consumed_watts: Option<f64>,
+    #[serde(rename="PowerCapacityWatts")]
+    pub power_capacity_watts: Option<f64>,
+}
+
+impl From<PowerControl> for PowerCap {
+    fn from(control: PowerControl) -> PowerCap {
+        PowerCap {
+            limit_in_watts: control.power_limit.limit_in_watts,
+            limit_exception: control.power_limit.limit_exception,
+            correction_in_ms: control.power_limit.correction_in_ms,
+            power_consumed_watts: control.power_consumed_watts,
+            power_capacity_watts: control.power_capacity_watts,
+        }
+    }
+}
+
+impl Render for PowerCap {
+    fn columns(&self) -> Vec<&'static str> {
+        vec!["LimitInWatts", "LimitException", "CorrectionInMs", "PowerConsumedWatts", "PowerCapacityWatts"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        vec![vec![
+            or_na(&self.limit_in_watts),
+            or_na(&self.limit_exception),
+            or_na(&self.correction_in_ms),
+            or_na(&self.power_consumed_watts),
+            or_na(&self.power_capacity_watts),
+        ]]
+    }
+
+    fn human(&self) -> String {
+        let mut out = String::new();
+        match self.limit_in_watts {
+            Some(limit) => out += &format!("Power cap:  {} W\n", limit),
+            None => out += "Power cap:  disabled\n",
+        }
+        out += &format!("Exception:  {}\n", or_na(&self.limit_exception));
+        out += &format!("Correction: {}\n", or_na(&self.correction_in_ms.map(|ms| format!("{} ms", ms))));
+        out += &format!("Consumed:   {}\n", or_na(&self.power_consumed_watts.map(|w| format!("{} W", w))));
+        out += &format!("Capacity:   {}\n", or_na(&self.power_capacity_watts.map(|w| format!("{} W", w))));
+        out
+    }
+}
+
+/// The power control a power cap applies to: the first one, as on every Dell chassis.
+pub(crate) fn power_control(status: PowerStatus, path: &str) -> Result<PowerControl, Error> {
+    status.power_control
+        .into_iter()
+        .next()
+        .ok_or_else(|| Error::NotFound(format!("power control in {}", path)))
+}
+
+pub async fn get_power_cap(client: &RedfishClient) -> Result<PowerCap, Error> {
+    let path = client.power().await?;
+    let status: PowerStatus = client.get(&path).await?;
+    Ok(power_control(status, &path)?.into())
+}
@@ -0,0 +1,169 @@
+use crate::error::Error;
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+use crate::client::RedfishClient;
+use crate::output::{or_na, Render};
+use crate::redundancy::{redundancy_modes, Redundancy, RedundancyLink, Status};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PowerStatus {
+    #[serde(rename="PowerControl", default)]
+    pub power_control: Vec<PowerControl>,
+    #[serde(rename="PowerSupplies", default)]
+    pub power_supplies: Vec<PowerSupply>,
+    #[serde(rename="Redundancy", default)]
+    pub redundancy: Vec<Redundancy>,
+    #[serde(rename="Oem", skip_serializing_if="Option::is_none")]
+    pub oem: Option<Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PowerControl {
+    #[serde(rename="Name")]
+    pub name: Option<String>,
+    #[serde(rename="PowerConsumedWatts")]
+    pub power_consumed_watts: Option<f64>,
+    #[serde(rename="PowerCapacityWatts")]
+    pub power_capacity_watts: Option<f64>,
+    #[serde(rename="PowerMetrics", default)]
+    pub power_metrics: PowerMetrics,
+    #[serde(rename="PowerLimit", default)]
+    pub power_limit: PowerLimit,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct PowerMetrics {
+    #[serde(rename="IntervalInMin")]
+    pub interval_in_min: Option<u64>,
+    #[serde(rename="MinConsumedWatts")]
+    pub min_consumed_watts: Option<f64>,
+    #[serde(rename="MaxConsumedWatts")]
+    pub max_consumed_watts: Option<f64>,
+    #[serde(rename="AverageConsumedWatts")]
+    pub average_consumed_watts: Option<f64>,
+}
+
+/// Power cap of a power control; `limit_in_watts` is null while capping is disabled.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct PowerLimit {
+    #[serde(rename="LimitInWatts")]
+    pub limit_in_watts: Option<f64>,
+    #[serde(rename="LimitException")]
+    pub limit_exception: Option<String>,
+    #[serde(rename="CorrectionInMs")]
+    pub correction_in_ms: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PowerSupply {
+    #[serde(rename="MemberId")]
+    pub member_id: Option<String>,
+    #[serde(rename="Name")]
+    pub name: Option<String>,
+    #[serde(rename="Model")]
+    pub model: Option<String>,
+    #[serde(rename="FirmwareVersion")]
+    pub firmware_version: Option<String>,
+    #[serde(rename="PowerSupplyType")]
+    pub power_supply_type: Option<String>,
+    #[serde(rename="LineInputVoltage")]
+    pub line_input_voltage: Option<f64>,
+    #[serde(rename="PowerCapacityWatts")]
+    pub power_capacity_watts: Option<f64>,
+    #[serde(rename="Redundancy", default)]
+    pub redundancy: Vec<RedundancyLink>,
+    #[serde(rename="Status", default)]
+    pub status: Status,
+}
+
+fn watts(value: Option<f64>) -> String {
+    or_na(&value.map(|w| format!("{} W", w)))
+}
+
+impl PowerSupply {
+    /// Id of the power supply, e.g. `PSU.Slot.1`, falling back to its name.
+    pub fn id(&self) -> String {
+        or_na(&self.member_id.as_ref().or(self.name.as_ref()))
+    }
+}
+
+impl Render for PowerStatus {
+    fn columns(&self) -> Vec<&'static str> {
+        vec![
+            "PowerConsumedWatts", "MinConsumedWatts", "MaxConsumedWatts", "AverageConsumedWatts", "LimitInWatts",
+            "PowerSupply", "Model", "FirmwareVersion", "LineInputVoltage", "PowerCapacityWatts", "Redundancy", "Health",
+        ]
+    }
+
+    /// One row per power supply, each repeating the readings of the first power control.
+    fn rows(&self) -> Vec<Vec<String>> {
+        let control = match self.power_control.first() {
+            Some(control) => vec![
+                or_na(&control.power_consumed_watts),
+                or_na(&control.power_metrics.min_consumed_watts),
+                or_na(&control.power_metrics.max_consumed_watts),
+                or_na(&control.power_metrics.average_consumed_watts),
+                or_na(&control.power_limit.limit_in_watts),
+            ],
+            None => vec![String::new(); 5],
+        };
+        if self.power_supplies.is_empty() {
+            let mut row = control;
+            row.extend(vec![String::new(); 7]);
+            return vec![row];
+        }
+
+        self.power_supplies.iter().map(|psu| {
+            let mut row = control.clone();
+            row.extend([
+                psu.id(),
+                or_na(&psu.model),
+                or_na(&psu.firmware_version),
+                or_na(&psu.line_input_voltage),
+                or_na(&psu.power_capacity_watts),
+                redundancy_modes(&psu.redundancy, &self.redundancy),
+                or_na(&psu.status.health),
+            ]);
+            row
+        }).collect()
+    }
+
+    fn human(&self) -> String {
+        let mut out = String::new();
+        for control in &self.power_control {
+            let interval = control.power_metrics.interval_in_min.map(|m| format!(" over the last {} min", m)).unwrap_or_default();
+            out += &format!("{}:\n", or_na(&control.name));
+            out += &format!("  Consumed:       {}\n", watts(control.power_consumed_watts));
+            out += &format!("  Minimum:        {}{}\n", watts(control.power_metrics.min_consumed_watts), interval);
+            out += &format!("  Maximum:        {}{}\n", watts(control.power_metrics.max_consumed_watts), interval);
+            out += &format!("  Average:        {}{}\n", watts(control.power_metrics.average_consumed_watts), interval);
+            out += &format!("  Capacity:       {}\n", watts(control.power_capacity_watts));
+            match control.power_limit.limit_in_watts {
+                Some(limit) => out += &format!("  Power cap:      {} W ({})\n", limit, or_na(&control.power_limit.limit_exception)),
+                None => out += "  Power cap:      disabled\n",
+            }
+        }
+        for group in &self.redundancy {
+            out += &format!("Redundancy:       {} {} ({})\n", or_na(&group.name), or_na(&group.mode), or_na(&group.status.health));
+        }
+        out += &format!("Found {} power supplies:\n", self.power_supplies.len());
+        for psu in &self.power_supplies {
+            out += &format!("- {}\n", psu.id());
+            out += &format!("  Model:          {}\n", or_na(&psu.model));
+            out += &format!("  Firmware:       {}\n", or_na(&psu.firmware_version));
+            out += &format!("  Input voltage:  {}\n", or_na(&psu.line_input_voltage.map(|v| format!("{} V", v))));
+            out += &format!("  Capacity:       {}\n", watts(psu.power_capacity_watts));
+            out += &format!("  Redundancy:     {}\n", redundancy_modes(&psu.redundancy, &self.redundancy));
+            out += &format!("  Status:         {}\n", if psu.status.state.as_deref() == Some("Enabled") {
+                or_na(&psu.status.health)
+            } else {
+                or_na(&psu.status.state)
+            });
+        }
+        out
+    }
+}
+
+pub async fn get_power_status(client: &RedfishClient) -> Result<PowerStatus, Error> {
+    client.get(&client.power().await?).await
+}
@@ -0,0 +1,41 @@
+use crate::error::Error;
+use serde::Deserialize;
+use crate::client::RedfishClient;
+use crate::output::{short_name, IdList};
+
+#[derive(Debug, Deserialize)]
+struct SystemActions {
+    #[serde(rename="Actions")]
+    actions: Option<Actions>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Actions {
+    #[serde(rename="#ComputerSystem.Reset")]
+    reset: Option<ResetAction>,
+}
+
+/// The ComputerSystem.Reset action of the selected system.
+#[derive(Debug, Deserialize)]
+pub(crate) struct ResetAction {
+    pub target: String,
+    /// Empty when the service does not advertise which reset types it accepts.
+    #[serde(rename="ResetType@Redfish.AllowableValues", default)]
+    pub allowable_values: Vec<String>,
+}
+
+pub(crate) async fn reset_action(client: &RedfishClient) -> Result<ResetAction, Error> {
+    let system = client.system().await?;
+    let response_json: SystemActions = client.get(&system).await?;
+
+    response_json.actions
+        .and_then(|a| a.reset)
+        .ok_or_else(|| Error::NotFound(format!("reset action on system '{}'", short_name(&system))))
+}
+
+pub async fn list_reset_types(client: &RedfishClient) -> Result<IdList, Error> {
+    Ok(IdList {
+        kind: "reset type",
+        ids: reset_action(client).await?.allowable_values,
+    })
+}
@@ -0,0 +1,127 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+use crate::error::Error;
+use serde::{Serialize, Deserialize};
+use serde_json::json;
+use crate::client::RedfishClient;
+use crate::output::{or_na, Render};
+use crate::power::list_reset_types::reset_action;
+
+/// How often the power state is polled while waiting for it to change.
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Reset types of the ComputerSystem.Reset action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ResetType {
+    On,
+    ForceOff,
+    GracefulShutdown,
+    GracefulRestart,
+    ForceRestart,
+    PowerCycle,
+    Nmi,
+}
+
+impl ResetType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ResetType::On => "On",
+            ResetType::ForceOff => "ForceOff",
+            ResetType::GracefulShutdown => "GracefulShutdown",
+            ResetType::GracefulRestart => "GracefulRestart",
+            ResetType::ForceRestart => "ForceRestart",
+            ResetType::PowerCycle => "PowerCycle",
+            ResetType::Nmi => "Nmi",
+        }
+    }
+
+    /// Power state the system settles in, for the reset types that change it for good.
+    pub fn target_state(self) -> Option<&'static str> {
+        match self {
+            ResetType::On => Some("On"),
+            ResetType::ForceOff | ResetType::GracefulShutdown => Some("Off"),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ResetType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SystemPowerState {
+    #[serde(rename="PowerState")]
+    power_state: Option<String>,
+}
+
+/// A requested reset, with the power state reached when it was waited for.
+#[derive(Debug, Serialize)]
+pub struct ResetResult {
+    #[serde(rename="ResetType")]
+    pub reset_type: ResetType,
+    #[serde(rename="PowerState", skip_serializing_if="Option::is_none")]
+    pub power_state: Option<String>,
+}
+
+impl Render for ResetResult {
+    fn columns(&self) -> Vec<&'static str> {
+        vec!["ResetType", "PowerState"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        vec![vec![self.reset_type.to_string(), or_na(&self.power_state)]]
+    }
+
+    fn human(&self) -> String {
+        let mut out = format!("Requested {} reset\n", self.reset_type);
+        if let Some(power_state) = &self.power_state {
+            out += &format!("Power state: {}\n", power_state);
+        }
+        out
+    }
+}
+
+/// Requests a reset of the selected system.
+///
+/// With `wait`, polls the system until it reaches the reset type's target power state, or
+/// fails with [`Error::Timeout`] once `wait` has passed. Reset types that end where they
+/// started (restarts, power cycles and NMIs) return right away.
+pub async fn reset_system(reset_type: ResetType, wait: Option<Duration>, client: &RedfishClient) -> Result<ResetResult, Error> {
+    let action = reset_action(client).await?;
+    if !action.allowable_values.is_empty() && !action.allowable_values.iter().any(|v| v == reset_type.as_str()) {
+        return Err(Error::Usage(format!(
+            "{} is not supported by this system, it allows: {}", reset_type, action.allowable_values.join(", ")
+        )));
+    }
+
+    client.post(&action.target, &json!({ "ResetType": reset_type.as_str() })).await?;
+
+    let power_state = match (wait, reset_type.target_state()) {
+        (Some(timeout), Some(state)) => Some(wait_for_power_state(state, timeout, client).await?),
+        _ => None,
+    };
+    Ok(ResetResult { reset_type, power_state })
+}
+
+/// Polls the selected system until its `PowerState` is `state`.
+pub async fn wait_for_power_state(state: &str, timeout: Duration, client: &RedfishClient) -> Result<String, Error> {
+    let system = client.system().await?;
+    let deadline = Instant::now() + timeout;
+    loop {
+        let current: SystemPowerState = client.get(&system).await?;
+        if current.power_state.as_deref() == Some(state) {
+            return Ok(state.to_owned());
+        }
+
+        let now = Instant::now();
+        if now >= deadline {
+            return Err(Error::Timeout(format!(
+                "power state is still {} after {} seconds, expected {}", or_na(&current.power_state), timeout.as_secs(), state
+            )));
+        }
+        tokio::time::sleep(POLL_INTERVAL.min(deadline - now)).await;
+    }
+}
@@ -0,0 +1,53 @@
+use clap::ValueEnum;
+use crate::error::Error;
+use serde::Serialize;
+use serde_json::json;
+use crate::client::RedfishClient;
+use crate::power::get_power_cap::{get_power_cap, power_control, PowerCap};
+use crate::power::get_power_status::PowerStatus;
+
+/// What the iDRAC does when consumption cannot be kept below the limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ValueEnum)]
+pub enum LimitException {
+    /// Take no action
+    NoAction,
+    /// Turn the system off
+    HardPowerOff,
+    /// Only log an event
+    LogEventOnly,
+    /// Vendor specific action
+    Oem,
+}
+
+/// Sets the chassis power limit, or removes it when `limit_in_watts` is `None`.
+///
+/// Limits of 0 W and above the power capacity of the chassis are refused with [`Error::Usage`]
+/// before anything is sent. The change is sent with the `ETag` read just before, so a limit
+/// changed by someone else in the meantime is not silently overwritten.
+pub async fn set_power_cap(limit_in_watts: Option<u32>, exception: Option<LimitException>, client: &RedfishClient) -> Result<PowerCap, Error> {
+    let path = client.power().await?;
+    let (status, etag): (PowerStatus, _) = client.get_with_etag(&path).await?;
+    let control = power_control(status, &path)?;
+    match (limit_in_watts, control.power_capacity_watts) {
+        (Some(0), _) => return Err(Error::Usage(String::from("a power cap of 0 W would keep the system from running, use `power cap clear` to remove the cap"))),
+        (Some(limit), Some(capacity)) if f64::from(limit) > capacity => {
+            return Err(Error::Usage(format!("a power cap of {} W is above the power capacity of {} W", limit, capacity)));
+        }
+        _ => {}
+    }
+
+    let mut limit = json!({ "LimitInWatts": limit_in_watts });
+    if let Some(exception) = exception {
+        limit["LimitException"] = json!(exception);
+    }
+    client.patch_if_match(&path, &json!({ "PowerControl": [{ "PowerLimit": limit }] }), etag.as_deref())
+        .await
+        .map_err(|e| e.precondition_failed_as("the power settings were changed by someone else in the meantime, try again"))?;
+
+    get_power_cap(client).await
+}
+
+/// Removes the chassis power limit.
+pub async fn clear_power_cap(client: &RedfishClient) -> Result<PowerCap, Error> {
+    set_power_cap(None, None, client).await
+}
@@ -0,0 +1,52 @@
+use serde::{Serialize, Deserialize};
+use crate::output::{or_na, NOT_AVAILABLE};
+
+/// Reference from a fan or power supply to a redundancy group it belongs to.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RedundancyLink {
+    #[serde(rename="@odata.id")]
+    pub name: String,
+}
+
+/// A redundancy group of fans or power supplies, e.g. `N+1`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Redundancy {
+    #[serde(rename="@odata.id")]
+    pub id: Option<String>,
+    #[serde(rename="Name")]
+    pub name: Option<String>,
+    #[serde(rename="Mode")]
+    pub mode: Option<String>,
+    #[serde(rename="MinNumNeeded")]
+    pub min_num_needed: Option<u32>,
+    #[serde(rename="Status", default)]
+    pub status: Status,
+}
+
+/// Status of a redundancy group, or of a sensor, fan or power supply in one.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Status {
+    #[serde(rename="Health")]
+    pub health: Option<String>,
+    #[serde(rename="State")]
+    pub state: Option<String>
+}
+
+impl Status {
+    /// Health while enabled, the state (e.g. `Disabled` or `Absent`) otherwise.
+    pub fn summary(&self) -> String {
+        match self.state.as_deref() {
+            Some("Enabled") | None => or_na(&self.health),
+            Some(state) => state.to_owned(),
+        }
+    }
+}
+
+/// Modes of the groups in `groups` that `links` point to, e.g. `N+1`.
+pub fn redundancy_modes(links: &[RedundancyLink], groups: &[Redundancy]) -> String {
+    let modes: Vec<String> = groups.iter()
+        .filter(|group| links.iter().any(|link| group.id.as_deref() == Some(link.name.as_str())))
+        .map(|group| or_na(&group.mode))
+        .collect();
+    if modes.is_empty() { NOT_AVAILABLE.to_owned() } else { modes.join(" ") }
+}
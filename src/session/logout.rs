@@ -0,0 +1,30 @@
+use crate::error::Error;
+use serde::Serialize;
+use crate::client::RedfishClient;
+use crate::output::Render;
+
+/// Outcome of logging out: whether there was a session to close.
+#[derive(Debug, Serialize)]
+pub struct Logout {
+    #[serde(rename="Closed")]
+    pub closed: bool,
+}
+
+impl Render for Logout {
+    fn columns(&self) -> Vec<&'static str> {
+        vec!["Closed"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        vec![vec![self.closed.to_string()]]
+    }
+
+    fn human(&self) -> String {
+        String::from(if self.closed { "Session closed\n" } else { "No active session\n" })
+    }
+}
+
+/// Deletes the current or cached session on the iDRAC and removes it from the cache.
+pub async fn logout(client: &RedfishClient) -> Result<Logout, Error> {
+    Ok(Logout { closed: client.logout().await? })
+}
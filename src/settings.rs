@@ -0,0 +1,160 @@
+use std::env;
+use std::path::{Path, PathBuf};
+use config::{Config, FileFormat};
+use serde_derive::Deserialize;
+use serde_json::{Map, Value};
+use crate::auth::AuthMode;
+use crate::credentials::{self, PASSWORD_KEYS};
+use crate::error::Error;
+use crate::paths;
+
+/// Extensions the settings file may have, in the order they are tried.
+const EXTENSIONS: [&str; 5] = ["toml", "json", "yaml", "yml", "ini"];
+
+/// Connection settings for a single iDRAC, as read from the settings file.
+#[derive(Debug, Deserialize)]
+pub struct Settings {
+    pub host: String,
+    pub user: String,
+    /// Plaintext password; prefer one of the other sources below.
+    pub password: Option<String>,
+    /// Name of an environment variable holding the password.
+    pub password_env: Option<String>,
+    /// File holding the password, which must not be readable by group or others.
+    pub password_file: Option<PathBuf>,
+    /// Shell command printing the password, e.g. `pass show idrac/root`.
+    pub password_command: Option<String>,
+    /// `session` (default) to log in once and reuse the X-Auth-Token, `basic` to send credentials on every request.
+    #[serde(default)]
+    pub auth: AuthMode,
+    /// Keep the session around between invocations instead of logging out on exit.
+    #[serde(default = "default_session_cache")]
+    pub session_cache: bool,
+    /// PEM file with additional CA certificates to trust, e.g. an internal CA that signed the iDRAC certificates.
+    pub ca_bundle: Option<PathBuf>,
+    /// SHA-256 fingerprint the iDRAC certificate must have, for self-signed certificates.
+    pub fingerprint: Option<String>,
+    /// Trust the certificate seen on the first connection and pin it from then on.
+    #[serde(default)]
+    pub trust_on_first_use: bool,
+    /// Skip certificate verification altogether.
+    #[serde(default)]
+    pub insecure: bool,
+    /// Id of the computer system to use when the service has several, e.g. on multi-node sleds.
+    pub system: Option<String>,
+    /// Id of the manager to use when the service has several.
+    pub manager: Option<String>,
+}
+
+fn default_session_cache() -> bool {
+    true
+}
+
+/// Which settings file and profile to load, usually straight from the command line.
+///
+/// Unset fields fall back to the `IDRAC_CONFIG`, `IDRAC_PROFILE` and `IDRAC_HOST` environment variables.
+#[derive(Debug, Default)]
+pub struct Selection {
+    pub config: Option<PathBuf>,
+    pub profile: Option<String>,
+    pub host: Option<String>,
+}
+
+impl Settings {
+    /// Loads the settings for the selected profile.
+    ///
+    /// Top-level keys of the settings file are defaults shared by every profile; a
+    /// `[hosts.<name>]` table overrides them for that profile. `IDRAC_HOST`, `IDRAC_USER`
+    /// and `IDRAC_PASSWORD` override the file, and `--host` overrides everything.
+    ///
+    /// The password is resolved before returning, so `password` is always set on success.
+    pub fn load(selection: &Selection) -> Result<Settings, Error> {
+        let mut settings = Settings::load_without_password(selection)?;
+        settings.password = Some(credentials::resolve_password(&settings)?);
+        Ok(settings)
+    }
+
+    /// Like [`load`](Self::load), leaving the password as configured in the file, for commands
+    /// that never log in such as `session logout`.
+    pub fn load_without_password(selection: &Selection) -> Result<Settings, Error> {
+        let file = match selection.config.clone().or_else(|| env::var_os("IDRAC_CONFIG").map(PathBuf::from)) {
+            Some(file) => file,
+            None => find_settings_file()
+                .ok_or_else(|| Error::Config(String::from("no settings file found, see the README for where to put one")))?,
+        };
+
+        let mut source = config::File::from(file.as_path());
+        if file.extension().is_none() {
+            source = source.format(FileFormat::Toml);
+        }
+        let content: Value = Config::builder()
+            .add_source(source)
+            .build()
+            .and_then(|c| c.try_deserialize())
+            .map_err(|e| Error::Config(e.to_string()))?;
+        let mut defaults = match content {
+            Value::Object(map) => map,
+            _ => Map::new(),
+        };
+        if contains_password(&defaults) && credentials::is_readable_by_others(&file, 0o004) {
+            return Err(Error::Config(format!(
+                "refusing to read a password from world-readable {}, run `chmod o-r` on it", file.display()
+            )));
+        }
+
+        let profiles = defaults.remove("hosts");
+        let default_profile = defaults.remove("default_profile").and_then(|p| p.as_str().map(str::to_owned));
+        let profile = selection.profile.clone()
+            .or_else(|| env::var("IDRAC_PROFILE").ok())
+            .or(default_profile);
+
+        if let Some(name) = profile {
+            let overrides = profiles.as_ref()
+                .and_then(|p| p.get(&name))
+                .and_then(Value::as_object)
+                .ok_or_else(|| Error::Config(format!("unknown profile '{}' in {}", name, file.display())))?;
+            if PASSWORD_KEYS.iter().any(|key| overrides.contains_key(*key)) {
+                defaults.retain(|key, _| !PASSWORD_KEYS.contains(&key.as_str()));
+            }
+            defaults.extend(overrides.clone());
+        }
+
+        for (key, variable) in [("host", "IDRAC_HOST"), ("user", "IDRAC_USER"), ("password", "IDRAC_PASSWORD")] {
+            if let Ok(value) = env::var(variable) {
+                defaults.insert(key.to_owned(), Value::String(value));
+            }
+        }
+        if let Some(host) = &selection.host {
+            defaults.insert(String::from("host"), Value::String(host.to_owned()));
+        }
+
+        serde_json::from_value(Value::Object(defaults))
+            .map_err(|e| Error::Config(format!("{} in {}", e, file.display())))
+    }
+}
+
+/// Whether the settings, or any of its profiles, holds a plaintext password.
+fn contains_password(settings: &Map<String, Value>) -> bool {
+    let in_profiles = settings.get("hosts")
+        .and_then(Value::as_object)
+        .map(|profiles| profiles.values().any(|p| p.get("password").is_some()))
+        .unwrap_or(false);
+    settings.contains_key("password") || in_profiles
+}
+
+/// Looks for a `settings` file in `$XDG_CONFIG_HOME/idrac-cli`, then in every `$XDG_CONFIG_DIRS`
+/// entry, then in the current directory.
+fn find_settings_file() -> Option<PathBuf> {
+    let mut dirs: Vec<PathBuf> = paths::config_dir().into_iter().collect();
+    let system_dirs = env::var("XDG_CONFIG_DIRS").ok().filter(|d| !d.is_empty()).unwrap_or_else(|| String::from("/etc/xdg"));
+    dirs.extend(env::split_paths(&system_dirs).map(|dir| dir.join("idrac-cli")));
+    dirs.push(PathBuf::from("."));
+
+    dirs.iter().find_map(|dir| settings_in(dir))
+}
+
+fn settings_in(dir: &Path) -> Option<PathBuf> {
+    EXTENSIONS.iter()
+        .map(|extension| dir.join("settings").with_extension(extension))
+        .find(|file| file.is_file())
+}
@@ -1,97 +1,113 @@
-use std::time::Duration;
-use reqwest::{Client, Error};
-use reqwest;
+use crate::error::Error;
 use serde::{Serialize, Deserialize};
-use crate::Settings;
+use serde_json::Value;
+use crate::client::RedfishClient;
+use crate::output::{or_na, short_name, Render};
 
 #[derive(Debug, Serialize, Deserialize)]
-struct StorageControllerInfo {
+pub struct StorageControllerInfo {
     #[serde(rename="Name")]
-    name: String,
-    #[serde(rename="Drives")]
-    drives: Vec<Drive>,
-    #[serde(rename="Status")]
-    status: Status,
-    #[serde(rename="StorageControllers")]
-    storage_controllers: Vec<StorageController>,
+    pub name: Option<String>,
+    #[serde(rename="Drives", default)]
+    pub drives: Vec<Drive>,
+    #[serde(rename="Status", default)]
+    pub status: Status,
+    #[serde(rename="StorageControllers", default)]
+    pub storage_controllers: Vec<StorageController>,
+    #[serde(rename="Oem", skip_serializing_if="Option::is_none")]
+    pub oem: Option<Value>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
-struct Drive {
+pub struct Drive {
     #[serde(rename="@odata.id")]
-    name: String
+    pub name: String
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct Status {
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Status {
     #[serde(rename="Health")]
-    health: String,
+    pub health: Option<String>,
     #[serde(rename="State")]
-    state: String
+    pub state: Option<String>
 }
 
 #[derive(Debug, Serialize, Deserialize)]
-struct StorageController {
+pub struct StorageController {
     #[serde(rename="@odata.id")]
-    name: String,
+    pub name: String,
     #[serde(rename="FirmwareVersion")]
-    firmware_version: String,
+    pub firmware_version: Option<String>,
     #[serde(rename="Manufacturer")]
-    manufacturer: String,
+    pub manufacturer: Option<String>,
     #[serde(rename="Model")]
-    model: String,
+    pub model: Option<String>,
     #[serde(rename="SpeedGbps")]
-    speed_gbps: u8,
-    #[serde(rename="Status")]
-    status: Status,
-    #[serde(rename="SupportedControllerProtocols")]
-    controller_protocols: Vec<String>,
-    #[serde(rename="SupportedDeviceProtocols")]
-    device_protocols: Vec<String>
+    pub speed_gbps: Option<f64>,
+    #[serde(rename="Status", default)]
+    pub status: Status,
+    #[serde(rename="SupportedControllerProtocols", default)]
+    pub controller_protocols: Vec<String>,
+    #[serde(rename="SupportedDeviceProtocols", default)]
+    pub device_protocols: Vec<String>
 }
 
-#[tokio::main]
-pub async fn get_storage_controller(storage_controller: &Option<String>, settings: Settings) -> Result<(), Error> {
-    let response = Client::builder()
-        .danger_accept_invalid_certs(true)
-        .timeout(Duration::from_secs(30))
-        .build()
-        .unwrap()
-        .get(format!("https://{}/redfish/v1/Systems/System.Embedded.1/Storage/{}", settings.host.to_owned(), storage_controller.as_ref().unwrap()))
-        .basic_auth(settings.user, Some(settings.password))
-        .send()
-        .await
-        .unwrap();
+impl Render for StorageControllerInfo {
+    fn columns(&self) -> Vec<&'static str> {
+        vec![
+            "Device", "Controller", "FirmwareVersion", "Manufacturer", "Model", "SpeedGbps", "Health", "State",
+            "ControllerProtocols", "DeviceProtocols", "Drives",
+        ]
+    }
 
-    let response_json: StorageControllerInfo = match response.json().await {
-        Ok(r) => r,
-        Err(e) => panic!("Could not introspect the token. Error was:\n {:?}", e),
-    };
+    fn rows(&self) -> Vec<Vec<String>> {
+        let drives: Vec<String> = self.drives.iter().map(|d| short_name(&d.name)).collect();
+        self.storage_controllers.iter().map(|controller| vec![
+            or_na(&self.name),
+            short_name(&controller.name),
+            or_na(&controller.firmware_version),
+            or_na(&controller.manufacturer),
+            or_na(&controller.model),
+            or_na(&controller.speed_gbps),
+            or_na(&controller.status.health),
+            or_na(&controller.status.state),
+            controller.controller_protocols.join(" "),
+            controller.device_protocols.join(" "),
+            drives.join(" "),
+        ]).collect()
+    }
 
-    println!("Device name: {}", response_json.name);
-    println!("Storage controller:");
-    for storage_controller in response_json.storage_controllers {
-        println!("- Name:             {}", &storage_controller.name.replace("/redfish/v1/Systems/System.Embedded.1/StorageControllers/", ""));
-        println!("  Firmware version: {}", storage_controller.firmware_version);
-        println!("  Device type:      {} {}", storage_controller.manufacturer, storage_controller.model);
-        println!("  Speed:            {} Gbps", storage_controller.speed_gbps);
-        if storage_controller.status.state == "Enabled" { println!("  Status: {}", storage_controller.status.health) }
-        println!("  Controller protocols:");
-        for protocol in storage_controller.controller_protocols {
-            println!("    - {}", protocol);
+    fn human(&self) -> String {
+        let mut out = String::new();
+        out += &format!("Device name: {}\n", or_na(&self.name));
+        out += "Storage controller:\n";
+        for storage_controller in &self.storage_controllers {
+            out += &format!("- Name:             {}\n", short_name(&storage_controller.name));
+            out += &format!("  Firmware version: {}\n", or_na(&storage_controller.firmware_version));
+            out += &format!("  Device type:      {} {}\n", or_na(&storage_controller.manufacturer), or_na(&storage_controller.model));
+            out += &format!("  Speed:            {}\n", or_na(&storage_controller.speed_gbps.map(|s| format!("{} Gbps", s))));
+            if storage_controller.status.state.as_deref() == Some("Enabled") {
+                out += &format!("  Status: {}\n", or_na(&storage_controller.status.health))
+            }
+            out += "  Controller protocols:\n";
+            for protocol in &storage_controller.controller_protocols {
+                out += &format!("    - {}\n", protocol);
+            }
+            out += "  Device protocols:\n";
+            for protocol in &storage_controller.device_protocols {
+                out += &format!("    - {}\n", protocol);
+            }
         }
-        println!("  Device protocols:");
-        for protocol in storage_controller.device_protocols {
-            println!("    - {}", protocol);
+        out += "Attached drives:\n";
+        for drive in &self.drives {
+            out += &format!("- {}\n", short_name(&drive.name));
         }
+        out
     }
-    println!("Attached drives:");
-    for drive in response_json.drives {
-        let long_name = &drive.name;
-        let short_name = long_name.replace("/redfish/v1/Systems/System.Embedded.1/Storage/Drives/", "");
-
-        println!("- {}", short_name)
-    }
+}
 
-    Ok(())
-}
\ No newline at end of file
+pub async fn get_storage_controller(storage_controller: &str, client: &RedfishClient) -> Result<StorageControllerInfo, Error> {
+    client.get(&format!("{}/{}", client.storage().await?, storage_controller))
+        .await
+        .map_err(|e| e.not_found_as(format!("storage controller '{}'", storage_controller)))
+}
@@ -1,89 +1,130 @@
-use std::time::Duration;
-use reqwest::{Client, Error};
-use reqwest;
+use crate::error::Error;
 use serde::{Serialize, Deserialize};
-use crate::lib::convert;
-use crate::Settings;
+use serde_json::Value;
+use crate::convert;
+use crate::client::RedfishClient;
+use crate::output::{or_na, short_name, Render};
 
 #[derive(Debug, Serialize, Deserialize)]
-struct DiskInfo {
+pub struct DiskInfo {
     #[serde(rename="BlockSizeBytes")]
-    block_size_bytes: u64,
+    pub block_size_bytes: Option<u64>,
     #[serde(rename="CapableSpeedGbs")]
-    capable_speed_gbps: u8,
+    pub capable_speed_gbps: Option<f64>,
     #[serde(rename="CapacityBytes")]
-    capacity_bytes: u64,
+    pub capacity_bytes: Option<u64>,
     #[serde(rename="Description")]
-    description: String,
+    pub description: Option<String>,
     #[serde(rename="FailurePredicted")]
-    failure_predicted: bool,
+    pub failure_predicted: Option<bool>,
     #[serde(rename="HotspareType")]
-    hotspare_type: String,
-    #[serde(rename="Links")]
-    links: Links,
+    pub hotspare_type: Option<String>,
+    #[serde(rename="Links", default)]
+    pub links: Links,
     #[serde(rename="Manufacturer")]
-    manufacturer: String,
+    pub manufacturer: Option<String>,
     #[serde(rename="MediaType")]
-    media_type: String,
+    pub media_type: Option<String>,
     #[serde(rename="Model")]
-    model: String,
+    pub model: Option<String>,
     #[serde(rename="NegotiatedSpeedGbs")]
-    negotaited_speed_gbps: u8,
+    pub negotaited_speed_gbps: Option<f64>,
     #[serde(rename="Protocol")]
-    protocol: String,
+    pub protocol: Option<String>,
+    /// Null for solid state drives.
     #[serde(rename="RotationSpeedRPM")]
-    rotation_speed_rpm: u64,
-    #[serde(rename="Status")]
-    status: Status
+    pub rotation_speed_rpm: Option<u64>,
+    #[serde(rename="Status", default)]
+    pub status: Status,
+    #[serde(rename="Oem", skip_serializing_if="Option::is_none")]
+    pub oem: Option<Value>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct Links {
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Links {
     #[serde(rename="Chassis")]
-    chassis: Chassis,
-    #[serde(rename="Volumes")]
-    volumes: Vec<Volume>
+    pub chassis: Option<Chassis>,
+    #[serde(rename="Volumes", default)]
+    pub volumes: Vec<Volume>
 }
 
 #[derive(Debug, Serialize, Deserialize)]
-struct Chassis {
+pub struct Chassis {
     #[serde(rename="@odata.id")]
-    name: String
+    pub name: String
 }
 
 #[derive(Debug, Serialize, Deserialize)]
-struct Volume {
+pub struct Volume {
     #[serde(rename="@odata.id")]
-    name: String
+    pub name: String
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct Status {
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Status {
     #[serde(rename="Health")]
-    health: String,
+    pub health: Option<String>,
     #[serde(rename="State")]
-    state: String
+    pub state: Option<String>
 }
 
-#[tokio::main]
-pub async fn get_storage_disk(disk: &Option<String>, settings: Settings) -> Result<(), Error> {
-    let response = Client::builder()
-        .danger_accept_invalid_certs(true)
-        .timeout(Duration::from_secs(30))
-        .build()
-        .unwrap()
-        .get(format!("https://{}/redfish/v1/Systems/System.Embedded.1/Storage/Drives/{}", settings.host.to_owned(), disk.as_ref().unwrap()))
-        .basic_auth(settings.user, Some(settings.password))
-        .send()
-        .await
-        .unwrap();
+impl Render for DiskInfo {
+    fn columns(&self) -> Vec<&'static str> {
+        vec![
+            "Description", "Manufacturer", "Model", "MediaType", "Protocol", "CapacityBytes", "BlockSizeBytes",
+            "CapableSpeedGbs", "NegotiatedSpeedGbs", "RotationSpeedRPM", "HotspareType", "FailurePredicted",
+            "Health", "State", "Volumes",
+        ]
+    }
 
-    let response_json: DiskInfo = match response.json().await {
-        Ok(r) => r,
-        Err(e) => panic!("Could not introspect the token. Error was:\n {:?}", e),
-    };
+    fn rows(&self) -> Vec<Vec<String>> {
+        let volumes: Vec<String> = self.links.volumes.iter().map(|v| short_name(&v.name)).collect();
+        vec![vec![
+            or_na(&self.description),
+            or_na(&self.manufacturer),
+            or_na(&self.model),
+            or_na(&self.media_type),
+            or_na(&self.protocol),
+            or_na(&self.capacity_bytes),
+            or_na(&self.block_size_bytes),
+            or_na(&self.capable_speed_gbps),
+            or_na(&self.negotaited_speed_gbps),
+            or_na(&self.rotation_speed_rpm),
+            or_na(&self.hotspare_type),
+            or_na(&self.failure_predicted),
+            or_na(&self.status.health),
+            or_na(&self.status.state),
+            volumes.join(" "),
+        ]]
+    }
 
-    println!("{:?}", response_json);
+    fn human(&self) -> String {
+        let mut out = String::new();
+        out += &format!("Description:       {}\n", or_na(&self.description));
+        out += &format!("Type:              {} {}\n", or_na(&self.manufacturer), or_na(&self.model));
+        out += &format!("Media:             {} ({})\n", or_na(&self.media_type), or_na(&self.protocol));
+        out += &format!("Capacity:          {}\n", or_na(&self.capacity_bytes.map(|b| convert(b as f64))));
+        out += &format!("Block size:        {}\n", or_na(&self.block_size_bytes.map(|b| format!("{} bytes", b))));
+        out += &format!(
+            "Speed:             {} (capable of {})\n",
+            or_na(&self.negotaited_speed_gbps.map(|s| format!("{} Gbps", s))),
+            or_na(&self.capable_speed_gbps.map(|s| format!("{} Gbps", s))),
+        );
+        out += &format!("Rotation speed:    {}\n", or_na(&self.rotation_speed_rpm.map(|rpm| format!("{} RPM", rpm))));
+        out += &format!("Hotspare type:     {}\n", or_na(&self.hotspare_type));
+        out += &format!("Failure predicted: {}\n", or_na(&self.failure_predicted));
+        if self.status.state.as_deref() == Some("Enabled") { out += &format!("Status:            {}\n", or_na(&self.status.health)) }
+        out += &format!("Chassis:           {}\n", or_na(&self.links.chassis.as_ref().map(|c| short_name(&c.name))));
+        out += "Volumes:\n";
+        for volume in &self.links.volumes {
+            out += &format!("- {}\n", short_name(&volume.name));
+        }
+        out
+    }
+}
 
-    Ok(())
-}
\ No newline at end of file
+pub async fn get_storage_disk(disk: &str, client: &RedfishClient) -> Result<DiskInfo, Error> {
+    client.get(&format!("{}/Drives/{}", client.storage().await?, disk))
+        .await
+        .map_err(|e| e.not_found_as(format!("disk '{}'", disk)))
+}
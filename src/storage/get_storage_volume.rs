@@ -1,79 +1,91 @@
-use std::time::Duration;
-use reqwest::{Client, Error};
-use reqwest;
+use crate::error::Error;
 use serde::{Serialize, Deserialize};
-use crate::lib::convert;
-use crate::Settings;
+use serde_json::Value;
+use crate::convert;
+use crate::client::RedfishClient;
+use crate::output::{or_na, short_name, Render};
 
 
 #[derive(Debug, Serialize, Deserialize)]
-struct StorageVolumeInfo {
+pub struct StorageVolumeInfo {
     #[serde(rename="Name")]
-    name: String,
+    pub name: Option<String>,
     #[serde(rename="Description")]
-    description: String,
+    pub description: Option<String>,
     #[serde(rename="BlockSizeBytes")]
-    block_size_bytes: u64,
+    pub block_size_bytes: Option<u64>,
     #[serde(rename="CapacityBytes")]
-    capacity_bytes: u64,
+    pub capacity_bytes: Option<u64>,
     #[serde(rename="Encrypted")]
-    encrypted: bool,
-    #[serde(rename="Links")]
-    links: Drives,
-    #[serde(rename="Status")]
-    status: Status,
+    pub encrypted: Option<bool>,
+    #[serde(rename="Links", default)]
+    pub links: Drives,
+    #[serde(rename="Status", default)]
+    pub status: Status,
     #[serde(rename="VolumeType")]
-    volume_type: String
+    pub volume_type: Option<String>,
+    #[serde(rename="Oem", skip_serializing_if="Option::is_none")]
+    pub oem: Option<Value>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct Drives {
-    #[serde(rename="Drives")]
-    drives: Vec<Drive>,
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Drives {
+    #[serde(rename="Drives", default)]
+    pub drives: Vec<Drive>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
-struct Drive {
+pub struct Drive {
     #[serde(rename="@odata.id")]
-    name: String,
+    pub name: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct Status {
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Status {
     #[serde(rename="Health")]
-    health: String,
+    pub health: Option<String>,
     #[serde(rename="State")]
-    state: String
+    pub state: Option<String>
 }
 
-#[tokio::main]
-pub async fn get_storage_volume(volume: &Option<String>, settings: Settings) -> Result<(), Error> {
-    let response = Client::builder()
-        .danger_accept_invalid_certs(true)
-        .timeout(Duration::from_secs(30))
-        .build()
-        .unwrap()
-        .get(format!("https://{}/redfish/v1/Systems/System.Embedded.1/Storage/Volumes/{}", settings.host.to_owned(), volume.as_ref().unwrap()))
-        .basic_auth(settings.user, Some(settings.password))
-        .send()
-        .await
-        .unwrap();
+impl Render for StorageVolumeInfo {
+    fn columns(&self) -> Vec<&'static str> {
+        vec!["Name", "Description", "VolumeType", "BlockSizeBytes", "CapacityBytes", "Encrypted", "Health", "State", "Drives"]
+    }
 
-    let response_json: StorageVolumeInfo = match response.json().await {
-        Ok(r) => r,
-        Err(e) => panic!("Could not introspect the token. Error was:\n {:?}", e),
-    };
+    fn rows(&self) -> Vec<Vec<String>> {
+        let drives: Vec<String> = self.links.drives.iter().map(|d| short_name(&d.name)).collect();
+        vec![vec![
+            or_na(&self.name),
+            or_na(&self.description),
+            or_na(&self.volume_type),
+            or_na(&self.block_size_bytes),
+            or_na(&self.capacity_bytes),
+            or_na(&self.encrypted),
+            or_na(&self.status.health),
+            or_na(&self.status.state),
+            drives.join(" "),
+        ]]
+    }
 
-    println!("Volume name: {}", response_json.name);
-    println!("Description: {}", response_json.description);
-    println!("Block size:  {} bytes", response_json.block_size_bytes);
-    println!("Capacity:    {}", convert(response_json.capacity_bytes as f64));
-    println!("Encrypted:   {}", response_json.encrypted);
-    if response_json.status.state == "Enabled" { println!("Status:      {}", response_json.status.health) }
-    println!("Drives:");
-    for drive in response_json.links.drives {
-        println!("- {}", &drive.name.replace("/redfish/v1/Systems/System.Embedded.1/Storage/Drives/", ""))
+    fn human(&self) -> String {
+        let mut out = String::new();
+        out += &format!("Volume name: {}\n", or_na(&self.name));
+        out += &format!("Description: {}\n", or_na(&self.description));
+        out += &format!("Block size:  {}\n", or_na(&self.block_size_bytes.map(|b| format!("{} bytes", b))));
+        out += &format!("Capacity:    {}\n", or_na(&self.capacity_bytes.map(|b| convert(b as f64))));
+        out += &format!("Encrypted:   {}\n", or_na(&self.encrypted));
+        if self.status.state.as_deref() == Some("Enabled") { out += &format!("Status:      {}\n", or_na(&self.status.health)) }
+        out += "Drives:\n";
+        for drive in &self.links.drives {
+            out += &format!("- {}\n", short_name(&drive.name));
+        }
+        out
     }
+}
 
-    Ok(())
-}
\ No newline at end of file
+pub async fn get_storage_volume(volume: &str, client: &RedfishClient) -> Result<StorageVolumeInfo, Error> {
+    client.get(&format!("{}/Volumes/{}", client.storage().await?, volume))
+        .await
+        .map_err(|e| e.not_found_as(format!("storage volume '{}'", volume)))
+}
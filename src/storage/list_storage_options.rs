@@ -1,14 +1,13 @@
-use std::time::Duration;
-use reqwest::{Client, Error};
-use reqwest;
+use crate::error::Error;
 use serde::{Serialize, Deserialize};
-use crate::Settings;
+use crate::client::RedfishClient;
+use crate::output::{short_name, IdList};
 
 #[derive(Debug, Serialize, Deserialize)]
 struct StorageControllerList {
     #[serde(rename="Name")]
-    name: String,
-    #[serde(rename="Members")]
+    name: Option<String>,
+    #[serde(rename="Members", default)]
     members: Vec<StorageControllerMember>
 }
 
@@ -18,31 +17,11 @@ struct StorageControllerMember {
     name: String
 }
 
-#[tokio::main]
-pub async fn list_storage_controllers(settings: Settings) -> Result<(), Error> {
-    let response = Client::builder()
-        .danger_accept_invalid_certs(true)
-        .timeout(Duration::from_secs(30))
-        .build()
-        .unwrap()
-        .get(format!("https://{}/redfish/v1/Systems/System.Embedded.1/Storage", settings.host.to_owned()))
-        .basic_auth(settings.user, Some(settings.password))
-        .send()
-        .await
-        .unwrap();
+pub async fn list_storage_controllers(client: &RedfishClient) -> Result<IdList, Error> {
+    let response_json: StorageControllerList = client.get(&client.storage().await?).await?;
 
-    let response_json: StorageControllerList = match response.json().await {
-        Ok(r) => r,
-        Err(e) => panic!("Could not introspect the token. Error was:\n {:?}", e),
-    };
-
-    println!("Found {} storage controller(s):", response_json.members.len());
-    for storage_controller in response_json.members {
-        let long_name = storage_controller.name;
-        let short_name = long_name.replace("/redfish/v1/Systems/System.Embedded.1/Storage/", "");
-
-        println!("- {}", short_name)
-    }
-
-    Ok(())
-}
\ No newline at end of file
+    Ok(IdList {
+        kind: "storage controller",
+        ids: response_json.members.iter().map(|m| short_name(&m.name)).collect(),
+    })
+}
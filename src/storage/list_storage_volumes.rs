@@ -1,15 +1,14 @@
-use std::time::Duration;
-use reqwest::{Client, Error};
-use reqwest;
+use crate::error::Error;
 use serde::{Serialize, Deserialize};
-use crate::Settings;
+use crate::client::RedfishClient;
+use crate::output::{short_name, IdList};
 
 
 #[derive(Debug, Serialize, Deserialize)]
 struct StorageVolumeInfo {
     #[serde(rename="@odata.id")]
     name: String,
-    #[serde(rename="Members")]
+    #[serde(rename="Members", default)]
     members: Vec<StorageVolumeMember>
 }
 
@@ -19,28 +18,13 @@ struct StorageVolumeMember {
     name: String
 }
 
-#[tokio::main]
-pub async fn list_storage_volumes(storage_controller: &Option<String>, settings: Settings) -> Result<(), Error> {
-    let response = Client::builder()
-        .danger_accept_invalid_certs(true)
-        .timeout(Duration::from_secs(30))
-        .build()
-        .unwrap()
-        .get(format!("https://{}/redfish/v1/Systems/System.Embedded.1/Storage/{}/Volumes", settings.host.to_owned(), storage_controller.as_ref().unwrap()))
-        .basic_auth(settings.user, Some(settings.password))
-        .send()
+pub async fn list_storage_volumes(storage_controller: &str, client: &RedfishClient) -> Result<IdList, Error> {
+    let response_json: StorageVolumeInfo = client.get(&format!("{}/{}/Volumes", client.storage().await?, storage_controller))
         .await
-        .unwrap();
+        .map_err(|e| e.not_found_as(format!("storage controller '{}'", storage_controller)))?;
 
-    let response_json: StorageVolumeInfo = match response.json().await {
-        Ok(r) => r,
-        Err(e) => panic!("Could not introspect the token. Error was:\n {:?}", e),
-    };
-
-    println!("Found {} storage volume(s):", response_json.members.len());
-    for volume in response_json.members {
-        println!("- {}", &volume.name.replace("/redfish/v1/Systems/System.Embedded.1/Storage/Volumes/", ""))
-    }
-
-    Ok(())
-}
\ No newline at end of file
+    Ok(IdList {
+        kind: "storage volume",
+        ids: response_json.members.iter().map(|m| short_name(&m.name)).collect(),
+    })
+}
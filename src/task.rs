@@ -0,0 +1,195 @@
+use std::time::{Duration, Instant};
+use reqwest::{Response, StatusCode};
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+use crate::client::RedfishClient;
+use crate::error::Error;
+use crate::output::{or_na, Render};
+
+/// How often a task is polled while waiting for it to finish.
+pub(crate) const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// A long-running operation the service runs in the background, e.g. a firmware update.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Task {
+    #[serde(rename="@odata.id", skip_serializing_if="Option::is_none")]
+    pub uri: Option<String>,
+    #[serde(rename="Id")]
+    pub id: Option<String>,
+    #[serde(rename="Name")]
+    pub name: Option<String>,
+    /// E.g. `New`, `Running`, `Completed`, `Exception` or `Cancelled`.
+    #[serde(rename="TaskState")]
+    pub task_state: Option<String>,
+    /// Health of the task: `OK`, `Warning` or `Critical`.
+    #[serde(rename="TaskStatus")]
+    pub task_status: Option<String>,
+    #[serde(rename="PercentComplete")]
+    pub percent_complete: Option<u32>,
+    #[serde(rename="StartTime")]
+    pub start_time: Option<String>,
+    #[serde(rename="EndTime")]
+    pub end_time: Option<String>,
+    #[serde(rename="Messages", default)]
+    pub messages: Vec<TaskMessage>,
+    /// On an iDRAC, the Lifecycle Controller job behind the task.
+    #[serde(rename="Oem", skip_serializing_if="Option::is_none")]
+    pub oem: Option<Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskMessage {
+    #[serde(rename="MessageId")]
+    pub message_id: Option<String>,
+    #[serde(rename="Message")]
+    pub message: Option<String>,
+}
+
+impl Task {
+    /// A task whose monitor answered without a task body, in the given state.
+    fn in_state(uri: &str, state: &str) -> Task {
+        Task { uri: Some(uri.to_owned()), task_state: Some(state.to_owned()), ..Default::default() }
+    }
+
+    pub fn is_successful(&self) -> bool {
+        self.task_state.as_deref() == Some("Completed") && !self.is_failed()
+    }
+
+    /// Whether the task ended without doing its work, including tasks that completed critically.
+    pub fn is_failed(&self) -> bool {
+        match self.task_state.as_deref() {
+            Some("Exception" | "Killed" | "Cancelled") => true,
+            Some("Completed") => self.task_status.as_deref() == Some("Critical"),
+            _ => false,
+        }
+    }
+
+    /// Whether the task waits for something outside the service, e.g. an update that is applied
+    /// on the next reset. The iDRAC reports those as scheduled jobs.
+    pub fn is_pending(&self) -> bool {
+        let job_state = self.oem.as_ref().and_then(|oem| oem.pointer("/Dell/JobState")).and_then(Value::as_str);
+        self.task_state.as_deref() == Some("Pending") || job_state == Some("Scheduled")
+    }
+
+    /// The most recent message, usually the one explaining the current state.
+    pub fn last_message(&self) -> Option<&str> {
+        self.messages.iter().rev().find_map(|m| m.message.as_deref())
+    }
+
+    fn label(&self) -> String {
+        or_na(&self.id.as_ref().or(self.uri.as_ref()))
+    }
+}
+
+impl Render for Task {
+    fn columns(&self) -> Vec<&'static str> {
+        vec!["Id", "Name", "TaskState", "TaskStatus", "PercentComplete", "Message"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        vec![vec![
+            self.label(),
+            or_na(&self.name),
+            or_na(&self.task_state),
+            or_na(&self.task_status),
+            or_na(&self.percent_complete),
+            or_na(&self.last_message()),
+        ]]
+    }
+
+    fn human(&self) -> String {
+        let mut out = String::new();
+        out += &format!("Task:     {}\n", self.label());
+        out += &format!("Name:     {}\n", or_na(&self.name));
+        out += &format!("State:    {}\n", or_na(&self.task_state));
+        out += &format!("Status:   {}\n", or_na(&self.task_status));
+        out += &format!("Progress: {}\n", or_na(&self.percent_complete.map(|p| format!("{}%", p))));
+        out += &format!("Start:    {}\n", or_na(&self.start_time));
+        out += &format!("End:      {}\n", or_na(&self.end_time));
+        if !self.messages.is_empty() {
+            out += "Messages:\n";
+            for message in &self.messages {
+                out += &format!("  - {}\n", or_na(&message.message));
+            }
+        }
+        out
+    }
+}
+
+/// Where to follow the task a request started: the `Location` of a 202 Accepted response.
+pub fn task_location(response: &Response) -> Option<String> {
+    if response.status() != StatusCode::ACCEPTED {
+        return None;
+    }
+    response.headers().get("Location").and_then(|v| v.to_str().ok()).map(str::to_owned)
+}
+
+impl RedfishClient {
+    /// Reads the state of a task, from its Task resource or from a task monitor.
+    ///
+    /// A task monitor answers 202 while the task runs and the result of the operation once it is
+    /// done, which may not be a task at all; that counts as completed.
+    pub async fn task(&self, uri: &str) -> Result<Task, Error> {
+        let (status, body) = self.get_raw(uri).await?;
+        let running = status == StatusCode::ACCEPTED;
+        if body.iter().all(u8::is_ascii_whitespace) {
+            return Ok(Task::in_state(uri, if running { "Running" } else { "Completed" }));
+        }
+
+        let value: Value = serde_json::from_slice(&body).map_err(|source| Error::Schema { path: uri.to_owned(), source })?;
+        if value.get("TaskState").is_none() {
+            return Ok(Task::in_state(uri, if running { "Running" } else { "Completed" }));
+        }
+        serde_json::from_value(value).map_err(|source| Error::Schema { path: uri.to_owned(), source })
+    }
+
+    /// Polls a task until it finishes, calling `progress` with every state read.
+    ///
+    /// Fails with [`Error::Failed`] when the task fails and with [`Error::Timeout`] when it is
+    /// still going after `timeout`. The iDRAC restarts for some tasks, e.g. updates of its own
+    /// firmware, so it being unreachable in between only fails the wait once `timeout` passes.
+    pub async fn wait_for_task(&self, uri: &str, timeout: Duration, progress: impl FnMut(&Task)) -> Result<Task, Error> {
+        self.poll_task(uri, timeout, Task::is_successful, progress).await
+    }
+
+    /// Like [`wait_for_task`](Self::wait_for_task), also returning once the task is pending, so
+    /// operations scheduled for the next reset do not keep the caller waiting until then.
+    pub async fn wait_for_task_or_pending(&self, uri: &str, timeout: Duration, progress: impl FnMut(&Task)) -> Result<Task, Error> {
+        self.poll_task(uri, timeout, |task| task.is_successful() || task.is_pending(), progress).await
+    }
+
+    async fn poll_task(&self, uri: &str, timeout: Duration, done: impl Fn(&Task) -> bool, mut progress: impl FnMut(&Task)) -> Result<Task, Error> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let task = match self.task(uri).await {
+                Ok(task) => task,
+                Err(e) if e.is_unavailable() && Instant::now() < deadline => {
+                    tokio::time::sleep(POLL_INTERVAL.min(deadline.saturating_duration_since(Instant::now()))).await;
+                    continue;
+                }
+                Err(e) if e.is_unavailable() => {
+                    return Err(Error::Timeout(format!("task {} could still not be read after {} seconds: {}", uri, timeout.as_secs(), e)));
+                }
+                Err(e) => return Err(e),
+            };
+            progress(&task);
+            if task.is_failed() {
+                return Err(Error::Failed(format!(
+                    "task {} ended {}: {}", task.label(), or_na(&task.task_state), or_na(&task.last_message())
+                )));
+            }
+            if done(&task) {
+                return Ok(task);
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(Error::Timeout(format!(
+                    "task {} is still {} at {}% after {} seconds",
+                    task.label(), or_na(&task.task_state), task.percent_complete.unwrap_or(0), timeout.as_secs()
+                )));
+            }
+            tokio::time::sleep(POLL_INTERVAL.min(deadline - now)).await;
+        }
+    }
+}
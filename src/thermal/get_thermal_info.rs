@@ -0,0 +1,193 @@
+use crate::error::Error;
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+use crate::client::RedfishClient;
+use crate::output::{highlighted_table, or_na, Level, Render, NOT_AVAILABLE};
+use crate::redundancy::{redundancy_modes, Redundancy, RedundancyLink, Status};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ThermalInfo {
+    #[serde(rename="Temperatures", default)]
+    pub temperatures: Vec<Temperature>,
+    #[serde(rename="Fans", default)]
+    pub fans: Vec<Fan>,
+    #[serde(rename="Redundancy", default)]
+    pub redundancy: Vec<Redundancy>,
+    #[serde(rename="Oem", skip_serializing_if="Option::is_none")]
+    pub oem: Option<Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Temperature {
+    #[serde(rename="MemberId")]
+    pub member_id: Option<String>,
+    #[serde(rename="Name")]
+    pub name: Option<String>,
+    #[serde(rename="ReadingCelsius")]
+    pub reading_celsius: Option<f64>,
+    #[serde(flatten)]
+    pub thresholds: Thresholds,
+    #[serde(rename="Status", default)]
+    pub status: Status,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Fan {
+    #[serde(rename="MemberId")]
+    pub member_id: Option<String>,
+    #[serde(rename="Name")]
+    pub name: Option<String>,
+    #[serde(rename="Reading")]
+    pub reading: Option<f64>,
+    /// `RPM` or `Percent`.
+    #[serde(rename="ReadingUnits")]
+    pub reading_units: Option<String>,
+    #[serde(flatten)]
+    pub thresholds: Thresholds,
+    #[serde(rename="Redundancy", default)]
+    pub redundancy: Vec<RedundancyLink>,
+    #[serde(rename="Status", default)]
+    pub status: Status,
+}
+
+/// Thresholds of a sensor, each null or missing when the sensor has none.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Thresholds {
+    #[serde(rename="LowerThresholdFatal", skip_serializing_if="Option::is_none")]
+    pub lower_fatal: Option<f64>,
+    #[serde(rename="LowerThresholdCritical")]
+    pub lower_critical: Option<f64>,
+    #[serde(rename="LowerThresholdNonCritical")]
+    pub lower_non_critical: Option<f64>,
+    #[serde(rename="UpperThresholdNonCritical")]
+    pub upper_non_critical: Option<f64>,
+    #[serde(rename="UpperThresholdCritical")]
+    pub upper_critical: Option<f64>,
+    #[serde(rename="UpperThresholdFatal", skip_serializing_if="Option::is_none")]
+    pub upper_fatal: Option<f64>,
+}
+
+impl Thresholds {
+    /// Which thresholds `reading` crosses; readings equal to a threshold count as crossing it.
+    pub fn level(&self, reading: Option<f64>) -> Level {
+        let reading = match reading {
+            Some(reading) => reading,
+            None => return Level::Ok,
+        };
+        let above = |threshold: Option<f64>| threshold.map(|t| reading >= t).unwrap_or(false);
+        let below = |threshold: Option<f64>| threshold.map(|t| reading <= t).unwrap_or(false);
+
+        if above(self.upper_critical) || above(self.upper_fatal) || below(self.lower_critical) || below(self.lower_fatal) {
+            Level::Critical
+        } else if above(self.upper_non_critical) || below(self.lower_non_critical) {
+            Level::Warning
+        } else {
+            Level::Ok
+        }
+    }
+}
+
+impl Temperature {
+    pub fn id(&self) -> String {
+        or_na(&self.name.as_ref().or(self.member_id.as_ref()))
+    }
+
+    /// The worse of the thresholds crossed and the reported health.
+    pub fn level(&self) -> Level {
+        self.thresholds.level(self.reading_celsius).max(Level::of_health(self.status.health.as_deref()))
+    }
+}
+
+impl Fan {
+    pub fn id(&self) -> String {
+        or_na(&self.name.as_ref().or(self.member_id.as_ref()))
+    }
+
+    /// The worse of the thresholds crossed and the reported health.
+    pub fn level(&self) -> Level {
+        self.thresholds.level(self.reading).max(Level::of_health(self.status.health.as_deref()))
+    }
+
+    fn reading_with_units(&self) -> String {
+        match (self.reading, self.reading_units.as_deref()) {
+            (Some(reading), Some("Percent")) => format!("{}%", reading),
+            (Some(reading), Some(units)) => format!("{} {}", reading, units),
+            (Some(reading), None) => reading.to_string(),
+            (None, _) => NOT_AVAILABLE.to_owned(),
+        }
+    }
+}
+
+impl Render for ThermalInfo {
+    fn columns(&self) -> Vec<&'static str> {
+        vec![
+            "Type", "Name", "Reading", "Units", "LowerThresholdCritical", "LowerThresholdNonCritical",
+            "UpperThresholdNonCritical", "UpperThresholdCritical", "Redundancy", "Health", "Level",
+        ]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        let temperatures = self.temperatures.iter().map(|sensor| vec![
+            String::from("Temperature"),
+            sensor.id(),
+            or_na(&sensor.reading_celsius),
+            String::from("Cel"),
+            or_na(&sensor.thresholds.lower_critical),
+            or_na(&sensor.thresholds.lower_non_critical),
+            or_na(&sensor.thresholds.upper_non_critical),
+            or_na(&sensor.thresholds.upper_critical),
+            String::new(),
+            or_na(&sensor.status.health),
+            sensor.level().to_string(),
+        ]);
+        let fans = self.fans.iter().map(|fan| vec![
+            String::from("Fan"),
+            fan.id(),
+            or_na(&fan.reading),
+            or_na(&fan.reading_units),
+            or_na(&fan.thresholds.lower_critical),
+            or_na(&fan.thresholds.lower_non_critical),
+            or_na(&fan.thresholds.upper_non_critical),
+            or_na(&fan.thresholds.upper_critical),
+            redundancy_modes(&fan.redundancy, &self.redundancy),
+            or_na(&fan.status.health),
+            fan.level().to_string(),
+        ]);
+        temperatures.chain(fans).collect()
+    }
+
+    fn human(&self) -> String {
+        let celsius = |value: Option<f64>| or_na(&value.map(|v| format!("{} °C", v)));
+        let mut out = String::from("Temperatures:\n");
+        let rows: Vec<Vec<String>> = self.temperatures.iter().map(|sensor| vec![
+            sensor.id(),
+            celsius(sensor.reading_celsius),
+            celsius(sensor.thresholds.upper_non_critical),
+            celsius(sensor.thresholds.upper_critical),
+            sensor.status.summary(),
+        ]).collect();
+        let levels: Vec<Level> = self.temperatures.iter().map(Temperature::level).collect();
+        out += &highlighted_table(&["Sensor", "Reading", "Warning above", "Critical above", "Status"], &rows, &levels);
+
+        out += "\nFans:\n";
+        let rows: Vec<Vec<String>> = self.fans.iter().map(|fan| vec![
+            fan.id(),
+            fan.reading_with_units(),
+            or_na(&fan.thresholds.lower_non_critical),
+            or_na(&fan.thresholds.lower_critical),
+            redundancy_modes(&fan.redundancy, &self.redundancy),
+            fan.status.summary(),
+        ]).collect();
+        let levels: Vec<Level> = self.fans.iter().map(Fan::level).collect();
+        out += &highlighted_table(&["Fan", "Reading", "Warning below", "Critical below", "Redundancy", "Status"], &rows, &levels);
+
+        for group in &self.redundancy {
+            out += &format!("\nRedundancy: {} {} ({})\n", or_na(&group.name), or_na(&group.mode), group.status.summary());
+        }
+        out
+    }
+}
+
+pub async fn get_thermal_info(client: &RedfishClient) -> Result<ThermalInfo, Error> {
+    client.get(&client.thermal().await?).await
+}
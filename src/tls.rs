@@ -0,0 +1,135 @@
+use std::fs;
+use std::io::{BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+use rustls::client::{ServerCertVerified, ServerCertVerifier};
+use rustls::{Certificate, ClientConfig, RootCertStore, ServerName};
+use sha2::{Digest, Sha256};
+use crate::error::Error;
+use crate::paths;
+use crate::settings::Settings;
+
+/// Builds the TLS configuration for a host according to its settings.
+///
+/// In order of precedence: `insecure` accepts any certificate, `fingerprint` pins the
+/// certificate's SHA-256 fingerprint, `trust_on_first_use` pins whatever certificate was
+/// seen on the first connection, and otherwise the chain is verified against the system
+/// trust store plus the optional `ca_bundle`.
+pub fn client_config(settings: &Settings) -> Result<ClientConfig, Error> {
+    let builder = ClientConfig::builder().with_safe_defaults();
+
+    let verifier: Arc<dyn ServerCertVerifier> = if settings.insecure {
+        Arc::new(AcceptAny)
+    } else if let Some(fingerprint) = &settings.fingerprint {
+        Arc::new(Pinned::new(&settings.host, Some(normalize(fingerprint)), None))
+    } else if settings.trust_on_first_use {
+        let known_hosts = paths::data_dir().map(|dir| dir.join("known_hosts"));
+        let known = known_hosts.as_deref().and_then(|file| known_fingerprint(file, &settings.host));
+        Arc::new(Pinned::new(&settings.host, known, known_hosts))
+    } else {
+        return Ok(builder
+            .with_root_certificates(root_store(settings.ca_bundle.as_deref())?)
+            .with_no_client_auth());
+    };
+
+    Ok(builder
+        .with_custom_certificate_verifier(verifier)
+        .with_no_client_auth())
+}
+
+/// Loads the system trust store, extended with the certificates from `ca_bundle`.
+fn root_store(ca_bundle: Option<&Path>) -> Result<RootCertStore, Error> {
+    let mut roots = RootCertStore::empty();
+    // A broken system store should not stop a configured CA bundle from working.
+    for cert in rustls_native_certs::load_native_certs().unwrap_or_default() {
+        let _ = roots.add(&Certificate(cert.0));
+    }
+
+    if let Some(path) = ca_bundle {
+        let bundle_error = |reason: String| Error::Config(format!("could not read CA bundle {}: {}", path.display(), reason));
+        let file = fs::File::open(path).map_err(|e| bundle_error(e.to_string()))?;
+        let certs = rustls_pemfile::certs(&mut BufReader::new(file)).map_err(|e| bundle_error(e.to_string()))?;
+        if certs.is_empty() {
+            return Err(bundle_error(String::from("no PEM certificates found")));
+        }
+        for cert in certs {
+            roots.add(&Certificate(cert)).map_err(|e| bundle_error(e.to_string()))?;
+        }
+    }
+    Ok(roots)
+}
+
+/// SHA-256 fingerprint of a DER encoded certificate, as lowercase hex.
+pub fn fingerprint(cert: &[u8]) -> String {
+    Sha256::digest(cert).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Accepts fingerprints written as `AB:CD:...`, `ab cd ...` or plain hex.
+fn normalize(fingerprint: &str) -> String {
+    fingerprint.chars().filter(|c| c.is_ascii_hexdigit()).collect::<String>().to_lowercase()
+}
+
+/// Looks up the fingerprint recorded for `host` in a `known_hosts` file of `<host> <sha256>` lines.
+fn known_fingerprint(file: &Path, host: &str) -> Option<String> {
+    fs::read_to_string(file).ok()?
+        .lines()
+        .filter_map(|line| line.split_once(' '))
+        .find(|(known, _)| *known == host)
+        .map(|(_, fingerprint)| normalize(fingerprint))
+}
+
+fn record_fingerprint(file: &Path, host: &str, fingerprint: &str) -> std::io::Result<()> {
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut known_hosts = fs::OpenOptions::new().create(true).append(true).open(file)?;
+    writeln!(known_hosts, "{} {}", host, fingerprint)
+}
+
+/// Used for `--insecure`: any certificate goes.
+struct AcceptAny;
+
+impl ServerCertVerifier for AcceptAny {
+    fn verify_server_cert(&self, _: &Certificate, _: &[Certificate], _: &ServerName, _: &mut dyn Iterator<Item = &[u8]>, _: &[u8], _: SystemTime) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+}
+
+/// Only accepts the certificate with the expected fingerprint, regardless of who signed it.
+///
+/// Without an expected fingerprint the first certificate seen is trusted and, when
+/// `known_hosts` is set, recorded there for the next invocations.
+struct Pinned {
+    host: String,
+    expected: Mutex<Option<String>>,
+    known_hosts: Option<PathBuf>,
+}
+
+impl Pinned {
+    fn new(host: &str, expected: Option<String>, known_hosts: Option<PathBuf>) -> Pinned {
+        Pinned { host: host.to_owned(), expected: Mutex::new(expected), known_hosts }
+    }
+}
+
+impl ServerCertVerifier for Pinned {
+    fn verify_server_cert(&self, end_entity: &Certificate, _: &[Certificate], _: &ServerName, _: &mut dyn Iterator<Item = &[u8]>, _: &[u8], _: SystemTime) -> Result<ServerCertVerified, rustls::Error> {
+        let seen = fingerprint(&end_entity.0);
+        let mut expected = self.expected.lock().unwrap_or_else(|e| e.into_inner());
+
+        match expected.as_ref() {
+            Some(pinned) if *pinned == seen => Ok(ServerCertVerified::assertion()),
+            Some(pinned) => Err(rustls::Error::General(format!(
+                "certificate fingerprint {} does not match the pinned fingerprint {}", seen, pinned
+            ))),
+            None => {
+                if let Some(file) = &self.known_hosts {
+                    record_fingerprint(file, &self.host, &seen)
+                        .map_err(|e| rustls::Error::General(format!("could not record certificate fingerprint: {}", e)))?;
+                }
+                *expected = Some(seen);
+                Ok(ServerCertVerified::assertion())
+            }
+        }
+    }
+}
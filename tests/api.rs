@@ -0,0 +1,1237 @@
+mod common;
+
+use std::cmp::Ordering;
+use std::time::Duration;
+use serde_json::{json, Value};
+use idrac_cli::bios::diff_bios_attributes::{diff_bios_attributes, Baseline, Change};
+use idrac_cli::bios::get_bios_attribute::get_bios_attribute;
+use idrac_cli::bios::list_bios_attributes::{list_bios_attributes, list_pending_bios_attributes};
+use idrac_cli::bios::set_bios_attributes::{set_bios_attributes, ApplyTime, MaintenanceWindow};
+use idrac_cli::boot::get_boot_settings::get_boot_settings;
+use idrac_cli::boot::set_boot_order::set_boot_order;
+use idrac_cli::boot::set_boot_override::{set_boot_override, BootMode, BootTarget};
+use idrac_cli::chassis::get_chassis_info::get_chassis_info;
+use idrac_cli::chassis::set_indicator::{set_indicator, Indicator};
+use idrac_cli::error::{self, Error};
+use idrac_cli::firmware::check_firmware::{check_firmware, compare_versions, Catalog, Compliance};
+use idrac_cli::firmware::list_firmware::{list_firmware, ComponentType};
+use idrac_cli::firmware::update_firmware::{update_firmware, Image, UpdateApplyTime};
+use idrac_cli::idrac::get_idrac_version::get_idrac_version;
+use idrac_cli::jobs::delete_jobs::clear_jobs;
+use idrac_cli::jobs::get_job::get_job;
+use idrac_cli::jobs::list_jobs::list_jobs;
+use idrac_cli::jobs::wait_for_job::{wait_for_job, wait_for_job_or_pending};
+use idrac_cli::logs::clear_log::clear_log;
+use idrac_cli::logs::list_log_entries::{list_log_entries, parse_time, Log, LogEntry, LogFilter};
+use idrac_cli::network::get_network_adapter::get_network_adapter;
+use idrac_cli::network::get_network_port::get_network_port;
+use idrac_cli::network::list_network_adapters::list_network_adapters;
+use idrac_cli::power::get_power_status::get_power_status;
+use idrac_cli::power::list_reset_types::list_reset_types;
+use idrac_cli::power::reset_system::{reset_system, ResetType};
+use idrac_cli::power::set_power_cap::{set_power_cap, LimitException};
+use idrac_cli::storage::get_storage_controller::get_storage_controller;
+use idrac_cli::storage::get_storage_disk::get_storage_disk;
+use idrac_cli::storage::get_storage_volume::get_storage_volume;
+use idrac_cli::storage::list_storage_options::list_storage_controllers;
+use idrac_cli::storage::list_storage_volumes::list_storage_volumes;
+use idrac_cli::output::Level;
+use idrac_cli::thermal::get_thermal_info::{get_thermal_info, Thresholds};
+use idrac_cli::task::task_location;
+use idrac_cli::RedfishClient;
+use wiremock::ResponseTemplate;
+use common::{certs_dir, fixture, fixtures_dir, MockIdrac, FINGERPRINT, SESSION};
+
+const CHASSIS: &str = "/redfish/v1/Chassis/System.Embedded.1";
+const POWER: &str = "/redfish/v1/Chassis/System.Embedded.1/Power";
+const RESET: &str = "/redfish/v1/Systems/System.Embedded.1/Actions/ComputerSystem.Reset";
+const JOBS: &str = "/redfish/v1/Managers/iDRAC.Embedded.1/Jobs";
+const TASK: &str = "/redfish/v1/TaskService/Tasks/JID_878700123456";
+const SYSTEM: &str = "/redfish/v1/Systems/System.Embedded.1";
+const BIOS: &str = "/redfish/v1/Systems/System.Embedded.1/Bios";
+const BIOS_SETTINGS: &str = "/redfish/v1/Systems/System.Embedded.1/Bios/Settings";
+const FIRMWARE: &str = "/redfish/v1/UpdateService/FirmwareInventory";
+const LCLOG_ENTRIES: &str = "/redfish/v1/Managers/iDRAC.Embedded.1/LogServices/Lclog/Entries";
+
+#[tokio::test]
+async fn reads_idrac_version_from_discovered_manager() {
+    let mock = MockIdrac::start().await;
+    let version = get_idrac_version(&mock.client()).await.unwrap();
+
+    assert_eq!(version.firmware_version.as_deref(), Some("6.10.30.00"));
+}
+
+#[tokio::test]
+async fn reads_chassis_linked_from_system() {
+    let mock = MockIdrac::start().await;
+    let chassis = get_chassis_info(&mock.client()).await.unwrap();
+
+    assert_eq!(chassis.model.as_deref(), Some("PowerEdge R640"));
+    assert_eq!(chassis.serial_number.as_deref(), Some("CN7475160G0187"));
+    assert_eq!(chassis.status.health.as_deref(), Some("OK"));
+}
+
+async fn patches(mock: &MockIdrac) -> Vec<Value> {
+    let requests = mock.server.received_requests().await.unwrap();
+    requests.iter().filter(|r| r.method.to_string() == "PATCH").map(|r| r.body_json().unwrap()).collect()
+}
+
+#[tokio::test]
+async fn blinks_indicator_led_on_older_firmware() {
+    let mock = MockIdrac::start().await;
+    mock.serve("PATCH", CHASSIS, 200, json!({})).await;
+
+    set_indicator(Indicator::Blink, None, &mock.client()).await.unwrap();
+    assert_eq!(patches(&mock).await, vec![json!({ "IndicatorLED": "Blinking" })]);
+}
+
+#[tokio::test]
+async fn prefers_location_indicator_when_supported() {
+    let mock = MockIdrac::start().await;
+    let mut chassis = fixture("chassis");
+    chassis["LocationIndicatorActive"] = json!(false);
+    mock.serve("GET", CHASSIS, 200, chassis).await;
+    mock.serve("PATCH", CHASSIS, 204, json!({})).await;
+
+    set_indicator(Indicator::Blink, Some(Duration::from_millis(10)), &mock.client()).await.unwrap();
+    assert_eq!(patches(&mock).await, vec![json!({ "LocationIndicatorActive": true }), json!({ "LocationIndicatorActive": false })]);
+}
+
+#[tokio::test]
+async fn indicator_is_only_switched_off_if_unchanged() {
+    let mock = MockIdrac::start().await;
+    let mut chassis = fixture("chassis");
+    chassis["LocationIndicatorActive"] = json!(false);
+    mock.respond("GET", CHASSIS, ResponseTemplate::new(200).insert_header("ETag", "W/\"gen-3\"").set_body_json(chassis)).await;
+    mock.serve_once("PATCH", CHASSIS, 204, json!({})).await;
+    mock.serve("PATCH", CHASSIS, 412, json!({ "error": { "message": "Precondition Failed" } })).await;
+
+    let err = set_indicator(Indicator::On, Some(Duration::from_millis(10)), &mock.client()).await.unwrap_err();
+    assert_eq!(err.to_string(), "iDRAC returned HTTP 412: the chassis was changed by someone else in the meantime, the indicator was left as it is");
+
+    let requests = mock.server.received_requests().await.unwrap();
+    let etags: Vec<&str> = requests.iter()
+        .filter(|r| r.method.to_string() == "PATCH")
+        .map(|r| r.headers.get(&"If-Match".into()).unwrap().as_str())
+        .collect();
+    assert_eq!(etags, vec!["W/\"gen-3\"", "W/\"gen-3\""]);
+}
+
+#[tokio::test]
+async fn lists_network_adapters_by_short_name() {
+    let mock = MockIdrac::start().await;
+    let adapters = list_network_adapters(&mock.client()).await.unwrap();
+
+    assert_eq!(adapters.ids, vec!["NIC.Integrated.1", "NIC.Slot.3"]);
+}
+
+#[tokio::test]
+async fn gets_network_adapter_with_port_details() {
+    let mock = MockIdrac::start().await;
+    let client = mock.client();
+
+    let adapter = get_network_adapter("NIC.Integrated.1", &client, false).await.unwrap();
+    assert_eq!(adapter.ports.iter().map(|p| p.id.as_str()).collect::<Vec<_>>(), vec!["NIC.Integrated.1-1", "NIC.Integrated.1-2"]);
+    assert!(adapter.ports.iter().all(|p| p.details.is_none()));
+
+    let detailed = get_network_adapter("NIC.Integrated.1", &client, true).await.unwrap();
+    let details = detailed.ports[1].details.as_ref().unwrap();
+    assert_eq!(details.link_status.as_deref(), Some("Down"));
+}
+
+#[tokio::test]
+async fn gets_network_port() {
+    let mock = MockIdrac::start().await;
+    let port = get_network_port("NIC.Slot.3", "NIC.Slot.3-1", &mock.client()).await.unwrap();
+
+    assert_eq!(port.addresses, vec!["3C:FD:FE:AA:BB:CC"]);
+    assert_eq!(port.supported_link_capabilities[0].speed_mbps, Some(25000));
+}
+
+#[tokio::test]
+async fn lists_and_gets_storage() {
+    let mock = MockIdrac::start().await;
+    let client = mock.client();
+
+    let controllers = list_storage_controllers(&client).await.unwrap();
+    assert_eq!(controllers.ids, vec!["RAID.Integrated.1-1", "AHCI.Embedded.1-1"]);
+
+    let controller = get_storage_controller("RAID.Integrated.1-1", &client).await.unwrap();
+    assert_eq!(controller.storage_controllers[0].speed_gbps, Some(12.0));
+    assert_eq!(controller.drives.len(), 2);
+
+    let volumes = list_storage_volumes("RAID.Integrated.1-1", &client).await.unwrap();
+    assert_eq!(volumes.ids, vec!["Disk.Virtual.0:RAID.Integrated.1-1"]);
+
+    let volume = get_storage_volume("Disk.Virtual.0:RAID.Integrated.1-1", &client).await.unwrap();
+    assert_eq!(volume.volume_type.as_deref(), Some("Mirrored"));
+
+    let disk = get_storage_disk("Disk.Bay.1:Enclosure.Internal.0-1:RAID.Integrated.1-1", &client).await.unwrap();
+    assert_eq!(disk.rotation_speed_rpm, Some(10000));
+}
+
+#[tokio::test]
+async fn reads_power_linked_from_chassis() {
+    let mock = MockIdrac::start().await;
+    let power = get_power_status(&mock.client()).await.unwrap();
+
+    assert_eq!(power.power_control[0].power_consumed_watts, Some(238.0));
+    assert_eq!(power.power_control[0].power_limit.limit_in_watts, None);
+    assert_eq!(power.power_supplies.len(), 2);
+    assert_eq!(power.power_supplies[0].id(), "PSU.Slot.1");
+}
+
+#[tokio::test]
+async fn sets_power_cap_if_unchanged() {
+    let mock = MockIdrac::start().await;
+    mock.respond("GET", POWER, ResponseTemplate::new(200).insert_header("ETag", "W/\"gen-7\"").set_body_json(fixture("power"))).await;
+    mock.serve("PATCH", POWER, 200, json!({})).await;
+
+    set_power_cap(Some(500), Some(LimitException::HardPowerOff), &mock.client()).await.unwrap();
+
+    let requests = mock.server.received_requests().await.unwrap();
+    let patch = requests.iter().find(|r| r.method.to_string() == "PATCH").unwrap();
+    assert_eq!(patch.headers.get(&"If-Match".into()).unwrap().as_str(), "W/\"gen-7\"");
+    assert_eq!(
+        patch.body_json::<Value>().unwrap(),
+        json!({ "PowerControl": [{ "PowerLimit": { "LimitInWatts": 500, "LimitException": "HardPowerOff" } }] })
+    );
+}
+
+#[tokio::test]
+async fn power_caps_outside_the_capacity_are_refused() {
+    let mock = MockIdrac::start().await;
+    for (watts, message) in [(0, "a power cap of 0 W would keep the system from running"), (1513, "a power cap of 1513 W is above the power capacity of 1512 W")] {
+        let err = set_power_cap(Some(watts), None, &mock.client()).await.unwrap_err();
+        assert_eq!(err.exit_code(), error::EXIT_USAGE);
+        assert!(err.to_string().starts_with(message), "{}", err);
+    }
+    let requests = mock.server.received_requests().await.unwrap();
+    assert!(!requests.iter().any(|r| r.method.to_string() == "PATCH"));
+
+    mock.serve("PATCH", POWER, 200, json!({})).await;
+    set_power_cap(Some(1512), None, &mock.client()).await.unwrap();
+}
+
+#[tokio::test]
+async fn concurrent_power_cap_changes_are_reported() {
+    let mock = MockIdrac::start().await;
+    mock.serve("PATCH", POWER, 412, json!({ "error": { "message": "Precondition Failed" } })).await;
+
+    let err = set_power_cap(Some(500), None, &mock.client()).await.unwrap_err();
+    assert_eq!(err.exit_code(), error::EXIT_REDFISH);
+    assert_eq!(err.to_string(), "iDRAC returned HTTP 412: the power settings were changed by someone else in the meantime, try again");
+}
+
+#[tokio::test]
+async fn reads_thermal_linked_from_chassis() {
+    let mock = MockIdrac::start().await;
+    let thermal = get_thermal_info(&mock.client()).await.unwrap();
+
+    let levels: Vec<Level> = thermal.temperatures.iter().map(|t| t.level()).collect();
+    assert_eq!(levels, vec![Level::Ok, Level::Warning, Level::Ok]);
+    assert_eq!(thermal.fans[1].reading, Some(360.0));
+    assert_eq!(thermal.fans[1].level(), Level::Critical);
+}
+
+#[test]
+fn readings_are_leveled_by_the_thresholds_they_cross() {
+    let thresholds = Thresholds {
+        lower_critical: Some(3.0),
+        lower_non_critical: Some(8.0),
+        upper_non_critical: Some(85.0),
+        upper_critical: Some(90.0),
+        ..Default::default()
+    };
+
+    assert_eq!(thresholds.level(Some(50.0)), Level::Ok);
+    assert_eq!(thresholds.level(Some(85.0)), Level::Warning);
+    assert_eq!(thresholds.level(Some(95.0)), Level::Critical);
+    assert_eq!(thresholds.level(Some(5.0)), Level::Warning);
+    assert_eq!(thresholds.level(Some(-1.0)), Level::Critical);
+    assert_eq!(thresholds.level(None), Level::Ok);
+    assert_eq!(Thresholds::default().level(Some(120.0)), Level::Ok);
+}
+
+#[tokio::test]
+async fn lists_reset_types() {
+    let mock = MockIdrac::start().await;
+    let reset_types = list_reset_types(&mock.client()).await.unwrap();
+
+    assert_eq!(reset_types.ids, vec!["On", "ForceOff", "ForceRestart", "GracefulRestart", "GracefulShutdown", "PushPowerButton", "Nmi", "PowerCycle"]);
+}
+
+#[tokio::test]
+async fn resets_through_the_advertised_action() {
+    let mock = MockIdrac::start().await;
+    mock.serve("POST", RESET, 204, json!({})).await;
+
+    let result = reset_system(ResetType::GracefulRestart, None, &mock.client()).await.unwrap();
+    assert_eq!(result.power_state, None);
+
+    let requests = mock.server.received_requests().await.unwrap();
+    let reset = requests.iter().find(|r| r.url.path() == RESET).unwrap();
+    assert_eq!(reset.body_json::<Value>().unwrap(), json!({ "ResetType": "GracefulRestart" }));
+}
+
+#[tokio::test]
+async fn reset_types_the_system_does_not_allow_are_refused() {
+    let mock = MockIdrac::start().await;
+    let mut system = fixture("system");
+    system["Actions"]["#ComputerSystem.Reset"]["ResetType@Redfish.AllowableValues"] = json!(["On", "ForceOff"]);
+    mock.serve("GET", "/redfish/v1/Systems/System.Embedded.1", 200, system).await;
+
+    let err = reset_system(ResetType::Nmi, None, &mock.client()).await.unwrap_err();
+    assert_eq!(err.exit_code(), error::EXIT_USAGE);
+    assert_eq!(err.to_string(), "Nmi is not supported by this system, it allows: On, ForceOff");
+}
+
+#[tokio::test]
+async fn waiting_for_a_power_state_times_out() {
+    let mock = MockIdrac::start().await;
+    mock.serve("POST", RESET, 204, json!({})).await;
+
+    let err = reset_system(ResetType::ForceOff, Some(Duration::from_secs(1)), &mock.client()).await.unwrap_err();
+    assert_eq!(err.exit_code(), error::EXIT_TIMEOUT);
+    assert_eq!(err.to_string(), "power state is still On after 1 seconds, expected Off");
+}
+
+#[tokio::test]
+async fn lists_bios_attributes_with_registry_details() {
+    let mock = MockIdrac::start().await;
+    let attributes = list_bios_attributes(&mock.client()).await.unwrap().attributes;
+
+    assert_eq!(attributes.len(), 7);
+    let profile = attributes.iter().find(|a| a.name == "SysProfile").unwrap();
+    assert_eq!(profile.current_value, json!("PerfPerWattOptimizedDapc"));
+    assert_eq!(profile.pending_value, Some(json!("PerfOptimized")));
+    assert_eq!(profile.display_name.as_deref(), Some("System Profile"));
+    assert_eq!(profile.allowed_values, vec!["PerfPerWattOptimizedDapc", "PerfOptimized", "Custom"]);
+
+    let pending = list_pending_bios_attributes(&mock.client()).await.unwrap().attributes;
+    assert_eq!(pending.iter().map(|a| a.name.as_str()).collect::<Vec<_>>(), vec!["SysProfile"]);
+}
+
+#[tokio::test]
+async fn bios_registry_falls_back_to_the_bios_resource() {
+    let mock = MockIdrac::start().await;
+    mock.serve("GET", "/redfish/v1/Registries/BiosAttributeRegistry.v1_0_3", 404, json!({})).await;
+
+    let tag = get_bios_attribute("SystemServiceTag", &mock.client()).await.unwrap();
+    assert!(tag.read_only);
+    assert_eq!(tag.display_name.as_deref(), Some("Service Tag"));
+
+    let err = get_bios_attribute("NoSuchAttribute", &mock.client()).await.unwrap_err();
+    assert_eq!(err.to_string(), "BIOS attribute 'NoSuchAttribute' was not found");
+}
+
+#[tokio::test]
+async fn sets_bios_attributes_through_the_settings_object() {
+    let mock = MockIdrac::start().await;
+    mock.respond("PATCH", BIOS_SETTINGS, ResponseTemplate::new(202)
+        .insert_header("Location", "/redfish/v1/Managers/iDRAC.Embedded.1/Jobs/JID_878632532911")).await;
+
+    let changes = [
+        (String::from("LogicalProc"), String::from("Disabled")),
+        (String::from("AcPwrRcvryUserDelay"), String::from("120")),
+    ];
+    let window = MaintenanceWindow { start: String::from("2026-10-20T22:00:00-05:00"), duration_in_seconds: 3600 };
+    let result = set_bios_attributes(&changes, ApplyTime::AtMaintenanceWindowStart, Some(window), &mock.client()).await.unwrap();
+    assert_eq!(result.job.as_deref(), Some("JID_878632532911"));
+
+    assert_eq!(patches(&mock).await, vec![json!({
+        "Attributes": { "AcPwrRcvryUserDelay": 120, "LogicalProc": "Disabled" },
+        "@Redfish.SettingsApplyTime": {
+            "ApplyTime": "AtMaintenanceWindowStart",
+            "MaintenanceWindowStartTime": "2026-10-20T22:00:00-05:00",
+            "MaintenanceWindowDurationInSeconds": 3600,
+        },
+    })]);
+}
+
+#[tokio::test]
+async fn bios_values_are_validated_before_sending() {
+    let mock = MockIdrac::start().await;
+    let client = mock.client();
+    let set = |name: &str, value: &str| [(name.to_owned(), value.to_owned())];
+
+    let err = set_bios_attributes(&set("AcPwrRcvryUserDelay", "30"), ApplyTime::OnReset, None, &client).await.unwrap_err();
+    assert_eq!(err.exit_code(), error::EXIT_USAGE);
+    assert_eq!(err.to_string(), "invalid value '30' for BIOS attribute AcPwrRcvryUserDelay: expected at least 60");
+
+    let err = set_bios_attributes(&set("BootMode", "Legacy"), ApplyTime::OnReset, None, &client).await.unwrap_err();
+    assert_eq!(err.to_string(), "invalid value 'Legacy' for BIOS attribute BootMode: expected one of Bios, Uefi");
+
+    let err = set_bios_attributes(&set("SystemServiceTag", "ABC1234"), ApplyTime::OnReset, None, &client).await.unwrap_err();
+    assert_eq!(err.to_string(), "BIOS attribute SystemServiceTag is read-only");
+
+    let err = set_bios_attributes(&set("NoSuchAttribute", "1"), ApplyTime::OnReset, None, &client).await.unwrap_err();
+    assert_eq!(err.exit_code(), error::EXIT_NOT_FOUND);
+
+    let err = set_bios_attributes(&set("LogicalProc", "Disabled"), ApplyTime::InMaintenanceWindowOnReset, None, &client).await.unwrap_err();
+    assert_eq!(err.to_string(), "apply time InMaintenanceWindowOnReset needs a maintenance window");
+
+    assert!(patches(&mock).await.is_empty());
+}
+
+#[tokio::test]
+async fn bios_apply_times_the_system_does_not_support_are_refused() {
+    let mock = MockIdrac::start().await;
+    let mut bios = fixture("bios");
+    bios["@Redfish.Settings"]["SupportedApplyTimes"] = json!(["OnReset"]);
+    mock.serve("GET", BIOS, 200, bios).await;
+
+    let changes = [(String::from("LogicalProc"), String::from("Disabled"))];
+    let err = set_bios_attributes(&changes, ApplyTime::Immediate, None, &mock.client()).await.unwrap_err();
+    assert_eq!(err.exit_code(), error::EXIT_USAGE);
+    assert_eq!(err.to_string(), "apply time Immediate is not supported by this system, it allows: OnReset");
+}
+
+#[tokio::test]
+async fn bios_values_follow_the_current_type_without_registry() {
+    let mock = MockIdrac::start().await;
+    mock.serve("GET", "/redfish/v1/Registries/BiosAttributeRegistry.v1_0_3", 404, json!({})).await;
+    mock.serve("GET", "/redfish/v1/Systems/System.Embedded.1/Bios/BiosRegistry", 404, json!({})).await;
+    mock.serve("PATCH", BIOS_SETTINGS, 200, json!({})).await;
+    let client = mock.client();
+
+    let changes = [(String::from("AcPwrRcvryUserDelay"), String::from("90"))];
+    set_bios_attributes(&changes, ApplyTime::Immediate, None, &client).await.unwrap();
+    assert_eq!(patches(&mock).await, vec![json!({
+        "Attributes": { "AcPwrRcvryUserDelay": 90 },
+        "@Redfish.SettingsApplyTime": { "ApplyTime": "Immediate" },
+    })]);
+
+    let changes = [(String::from("AcPwrRcvryUserDelay"), String::from("soon"))];
+    let err = set_bios_attributes(&changes, ApplyTime::Immediate, None, &client).await.unwrap_err();
+    assert_eq!(err.to_string(), "invalid value 'soon' for BIOS attribute AcPwrRcvryUserDelay: expected an integer");
+}
+
+#[tokio::test]
+async fn bios_diff_reports_added_removed_and_changed_attributes() {
+    let mock = MockIdrac::start().await;
+    let file = std::env::temp_dir().join(format!("idrac-cli-baseline-{}.yaml", mock.server.address().port()));
+    std::fs::write(&file, "\
+AcPwrRcvryUserDelay: 60
+AssetTag: ''
+BootMode: Bios
+LogicalProc: Enabled
+SysProfile: PerfPerWattOptimizedDapc
+SystemServiceTag: 1ABCD23
+NodeInterleave: Disabled
+").unwrap();
+
+    let baseline = Baseline::read(&file).unwrap();
+    let diff = diff_bios_attributes(&baseline, &[], &mock.client()).await.unwrap();
+    let changes: Vec<(&str, Change)> = diff.differences.iter().map(|d| (d.name.as_str(), d.change)).collect();
+    assert_eq!(changes, vec![
+        ("BootMode", Change::Changed),
+        ("NodeInterleave", Change::Removed),
+        ("ProcVirtualization", Change::Added),
+    ]);
+    assert_eq!(diff.differences[0].baseline_value, Some(json!("Bios")));
+
+    let err = diff.check().unwrap_err();
+    assert_eq!(err.exit_code(), error::EXIT_DRIFT);
+    assert_eq!(err.to_string(), format!("3 BIOS attribute(s) differ from {}", file.display()));
+
+    let ignore = [String::from("BootMode"), String::from("NodeInterleave"), String::from("ProcVirtualization")];
+    let diff = diff_bios_attributes(&baseline, &ignore, &mock.client()).await.unwrap();
+    assert!(diff.check().is_ok());
+}
+
+#[tokio::test]
+async fn bios_baselines_are_read_from_saved_resources_and_lists() {
+    let dir = std::env::temp_dir();
+    let resource = dir.join("idrac-cli-baseline-resource.json");
+    std::fs::write(&resource, fixture("bios").to_string()).unwrap();
+    assert_eq!(Baseline::read(&resource).unwrap().attributes["AcPwrRcvryUserDelay"], json!(60));
+
+    let list = dir.join("idrac-cli-baseline-list.json");
+    std::fs::write(&list, r#"[{ "Name": "BootMode", "CurrentValue": "Uefi", "ReadOnly": false }]"#).unwrap();
+    assert_eq!(Baseline::read(&list).unwrap().attributes["BootMode"], json!("Uefi"));
+
+    let invalid = dir.join("idrac-cli-baseline-invalid.json");
+    std::fs::write(&invalid, "[1, 2]").unwrap();
+    let err = Baseline::read(&invalid).unwrap_err();
+    assert_eq!(err.exit_code(), error::EXIT_USAGE);
+    assert_eq!(err.to_string(), format!("invalid baseline {}: expected Name and CurrentValue in every list entry", invalid.display()));
+}
+
+#[tokio::test]
+async fn reads_boot_order_with_option_names() {
+    let mock = MockIdrac::start().await;
+    let settings = get_boot_settings(&mock.client()).await.unwrap();
+
+    assert_eq!(settings.boot.boot_order, vec!["Boot0001", "Boot0002", "Boot0000"]);
+    assert_eq!(settings.boot.override_enabled.as_deref(), Some("Disabled"));
+    assert!(settings.boot.allowed_targets.iter().any(|t| t == "UefiHttp"));
+    let pxe = settings.option("Boot0002").unwrap();
+    assert_eq!(pxe.display_name.as_deref(), Some("PXE Device 1: Integrated NIC 1 Port 1 Partition 1"));
+    assert_eq!(pxe.enabled, Some(false));
+}
+
+#[tokio::test]
+async fn sets_one_time_boot_if_unchanged() {
+    let mock = MockIdrac::start().await;
+    mock.respond("GET", SYSTEM, ResponseTemplate::new(200).insert_header("ETag", "W/\"gen-3\"").set_body_json(fixture("system"))).await;
+    mock.serve("PATCH", SYSTEM, 200, json!({})).await;
+
+    set_boot_override(BootTarget::Pxe, Some(BootMode::Uefi), &mock.client()).await.unwrap();
+
+    let requests = mock.server.received_requests().await.unwrap();
+    let patch = requests.iter().find(|r| r.method.to_string() == "PATCH").unwrap();
+    assert_eq!(patch.headers.get(&"If-Match".into()).unwrap().as_str(), "W/\"gen-3\"");
+    assert_eq!(patch.body_json::<Value>().unwrap(), json!({
+        "Boot": { "BootSourceOverrideTarget": "Pxe", "BootSourceOverrideEnabled": "Once", "BootSourceOverrideMode": "UEFI" }
+    }));
+}
+
+#[tokio::test]
+async fn boot_targets_the_system_does_not_allow_are_refused() {
+    let mock = MockIdrac::start().await;
+    let mut system = fixture("system");
+    system["Boot"]["BootSourceOverrideTarget@Redfish.AllowableValues"] = json!(["None", "Pxe", "Hdd"]);
+    mock.serve("GET", SYSTEM, 200, system).await;
+
+    let err = set_boot_override(BootTarget::UefiHttp, None, &mock.client()).await.unwrap_err();
+    assert_eq!(err.exit_code(), error::EXIT_USAGE);
+    assert_eq!(err.to_string(), "UefiHttp is not supported by this system, it allows: None, Pxe, Hdd");
+    assert!(patches(&mock).await.is_empty());
+}
+
+#[tokio::test]
+async fn moves_boot_options_to_the_front() {
+    let mock = MockIdrac::start().await;
+    mock.serve("PATCH", SYSTEM, 200, json!({})).await;
+    let client = mock.client();
+
+    let first = [String::from("PXE Device 1: Integrated NIC 1 Port 1 Partition 1"), String::from("Boot0000")];
+    let settings = set_boot_order(&first, &client).await.unwrap();
+    assert_eq!(patches(&mock).await, vec![json!({ "Boot": { "BootOrder": ["Boot0002", "Boot0000", "Boot0001"] } })]);
+    assert_eq!(settings.boot.boot_order, ["Boot0002", "Boot0000", "Boot0001"]);
+    assert!(settings.is_pending());
+    assert_eq!(settings.job, None);
+
+    let err = set_boot_order(&[String::from("Boot0009")], &client).await.unwrap_err();
+    assert_eq!(err.to_string(), "boot option 'Boot0009' was not found");
+
+    let err = set_boot_order(&[String::from("Boot0001"), String::from("Red Hat Enterprise Linux")], &client).await.unwrap_err();
+    assert_eq!(err.exit_code(), error::EXIT_USAGE);
+    assert_eq!(err.to_string(), "boot option Boot0001 is given more than once");
+}
+
+#[tokio::test]
+async fn boot_orders_go_to_the_settings_object() {
+    let mock = MockIdrac::start().await;
+    let settings_object = format!("{}/Settings", SYSTEM);
+    let mut system = fixture("system");
+    system["@Redfish.Settings"] = json!({ "SettingsObject": { "@odata.id": settings_object } });
+    mock.respond("GET", SYSTEM, ResponseTemplate::new(200).insert_header("ETag", "W/\"gen-3\"").set_body_json(system)).await;
+    mock.respond("GET", &settings_object, ResponseTemplate::new(200).insert_header("ETag", "W/\"gen-8\"").set_body_json(json!({}))).await;
+    mock.respond("PATCH", &settings_object, ResponseTemplate::new(202)
+        .insert_header("Location", "/redfish/v1/Managers/iDRAC.Embedded.1/Jobs/JID_878632532911")).await;
+
+    let settings = set_boot_order(&[String::from("Boot0002")], &mock.client()).await.unwrap();
+    assert_eq!(settings.job.as_deref(), Some("JID_878632532911"));
+    assert_eq!(settings.job_state, None);
+
+    let requests = mock.server.received_requests().await.unwrap();
+    let patch = requests.iter().find(|r| r.method.to_string() == "PATCH").unwrap();
+    assert_eq!(patch.url.path(), settings_object);
+    assert_eq!(patch.headers.get(&"If-Match".into()).unwrap().as_str(), "W/\"gen-8\"");
+}
+
+#[tokio::test]
+async fn lists_jobs_in_the_queue() {
+    let mock = MockIdrac::start().await;
+    let jobs = list_jobs(&mock.client()).await.unwrap().jobs;
+
+    let states: Vec<(&str, Option<&str>)> = jobs.iter().map(|j| (j.id.as_str(), j.job_state.as_deref())).collect();
+    assert_eq!(states, vec![("JID_878632532911", Some("Completed")), ("JID_878659984891", Some("Scheduled"))]);
+
+    let err = get_job("JID_000000000001", &mock.client()).await.unwrap_err();
+    assert_eq!(err.to_string(), "job 'JID_000000000001' was not found");
+}
+
+#[tokio::test]
+async fn waiting_for_a_job_reports_progress_and_failure() {
+    let mock = MockIdrac::start().await;
+    let mut job = fixture("job_firmware");
+    job["JobState"] = json!("Failed");
+    job["PercentComplete"] = json!(40);
+    job["Message"] = json!("Unable to verify the update package signature.");
+    mock.serve("GET", &format!("{}/JID_878659984891", JOBS), 200, job).await;
+    let client = mock.client();
+
+    let done = wait_for_job("JID_878632532911", Duration::from_secs(1), |_| {}, &client).await.unwrap();
+    assert_eq!(done.percent_complete, Some(100));
+
+    let mut seen = Vec::new();
+    let err = wait_for_job("JID_878659984891", Duration::from_secs(1), |job| seen.push(job.percent_complete), &client).await.unwrap_err();
+    assert_eq!(seen, vec![Some(40)]);
+    assert_eq!(err.exit_code(), error::EXIT_FAILED);
+    assert_eq!(err.to_string(), "job JID_878659984891 failed: Unable to verify the update package signature.");
+}
+
+#[tokio::test]
+async fn waiting_for_a_scheduled_job_times_out() {
+    let mock = MockIdrac::start().await;
+    let err = wait_for_job("JID_878659984891", Duration::ZERO, |_| {}, &mock.client()).await.unwrap_err();
+
+    assert_eq!(err.exit_code(), error::EXIT_TIMEOUT);
+    assert_eq!(err.to_string(), "job JID_878659984891 is still Scheduled at 0% after 0 seconds");
+
+    let job = wait_for_job_or_pending("JID_878659984891", Duration::ZERO, |_| {}, &mock.client()).await.unwrap();
+    assert!(job.is_pending());
+}
+
+#[tokio::test]
+async fn clears_the_job_queue_through_the_dell_job_service() {
+    let mock = MockIdrac::start().await;
+    let action = "/redfish/v1/Managers/iDRAC.Embedded.1/Oem/Dell/DellJobService/Actions/DellJobService.DeleteJobQueue";
+    mock.serve("POST", action, 200, json!({})).await;
+
+    let deleted = clear_jobs(&mock.client()).await.unwrap();
+    assert_eq!(deleted.ids, vec!["JID_878632532911", "JID_878659984891"]);
+
+    let requests = mock.server.received_requests().await.unwrap();
+    let clear = requests.iter().find(|r| r.url.path() == action).unwrap();
+    assert_eq!(clear.body_json::<Value>().unwrap(), json!({ "JobID": "JID_CLEARALL" }));
+}
+
+#[tokio::test]
+async fn clears_the_job_queue_job_by_job_without_the_dell_job_service() {
+    let mock = MockIdrac::start().await;
+    mock.serve("DELETE", &format!("{}/JID_878632532911", JOBS), 200, json!({})).await;
+    mock.serve("DELETE", &format!("{}/JID_878659984891", JOBS), 200, json!({})).await;
+
+    clear_jobs(&mock.client()).await.unwrap();
+
+    let requests = mock.server.received_requests().await.unwrap();
+    let deleted: Vec<&str> = requests.iter().filter(|r| r.method.to_string() == "DELETE").map(|r| r.url.path()).collect();
+    assert_eq!(deleted, vec![
+        "/redfish/v1/Managers/iDRAC.Embedded.1/Jobs/JID_878632532911",
+        "/redfish/v1/Managers/iDRAC.Embedded.1/Jobs/JID_878659984891",
+    ]);
+}
+
+#[tokio::test]
+async fn follows_a_task_until_it_completes() {
+    let mock = MockIdrac::start().await;
+    let mut seen = Vec::new();
+    let task = mock.client().wait_for_task(TASK, Duration::from_secs(1), |t| seen.push(t.task_state.clone())).await.unwrap();
+
+    assert_eq!(seen, vec![Some(String::from("Completed"))]);
+    assert_eq!(task.last_message(), Some("The specified job has completed successfully."));
+}
+
+#[tokio::test]
+async fn full_urls_are_only_followed_to_the_idrac() {
+    let mock = MockIdrac::start().await;
+    let client = mock.client();
+    assert_eq!(client.url(TASK), format!("{}{}", mock.uri(), TASK));
+    assert_eq!(client.url(&format!("{}{}", mock.uri(), TASK)), format!("{}{}", mock.uri(), TASK));
+    // An iDRAC behind NAT may name itself by an address the client cannot reach.
+    assert_eq!(client.url(&format!("https://192.168.0.120{}?$expand=.", TASK)), format!("{}{}?$expand=.", mock.uri(), TASK));
+    let other_port = format!("http://127.0.0.1:{}{}", mock.server.address().port() + 1, TASK);
+    assert_eq!(client.url(&other_port), format!("{}{}", mock.uri(), TASK));
+
+    let task = client.wait_for_task(&format!("https://attacker.example.com{}", TASK), Duration::from_secs(1), |_| {}).await.unwrap();
+    assert_eq!(task.task_state.as_deref(), Some("Completed"));
+}
+
+#[tokio::test]
+async fn task_monitors_answer_accepted_until_the_operation_is_done() {
+    let mock = MockIdrac::start().await;
+    mock.respond("GET", "/redfish/v1/TaskService/TaskMonitors/running", ResponseTemplate::new(202)).await;
+    mock.respond("GET", "/redfish/v1/TaskService/TaskMonitors/done", ResponseTemplate::new(204)).await;
+    mock.serve("POST", "/redfish/v1/Actions/Done", 200, json!({})).await;
+    mock.respond("POST", "/redfish/v1/Actions/Accepted", ResponseTemplate::new(202)
+        .insert_header("Location", "/redfish/v1/TaskService/TaskMonitors/running")).await;
+    let client = mock.client();
+
+    let running = client.task("/redfish/v1/TaskService/TaskMonitors/running").await.unwrap();
+    assert_eq!(running.task_state.as_deref(), Some("Running"));
+    assert!(!running.is_successful() && !running.is_failed());
+    let done = client.task("/redfish/v1/TaskService/TaskMonitors/done").await.unwrap();
+    assert!(done.is_successful());
+
+    let accepted = client.post("/redfish/v1/Actions/Accepted", &json!({})).await.unwrap();
+    assert_eq!(task_location(&accepted).as_deref(), Some("/redfish/v1/TaskService/TaskMonitors/running"));
+    let finished = client.post("/redfish/v1/Actions/Done", &json!({})).await.unwrap();
+    assert_eq!(task_location(&finished), None);
+}
+
+#[tokio::test]
+async fn failed_and_slow_tasks_are_reported() {
+    let mock = MockIdrac::start().await;
+    let mut task = fixture("task");
+    task["TaskState"] = json!("Exception");
+    task["TaskStatus"] = json!("Critical");
+    task["Messages"] = json!([{ "Message": "Unable to transfer the image file." }]);
+    mock.serve("GET", TASK, 200, task).await;
+    let mut running = fixture("task");
+    running["TaskState"] = json!("Running");
+    running["PercentComplete"] = json!(35);
+    mock.serve("GET", "/redfish/v1/TaskService/Tasks/JID_878700654321", 200, running).await;
+    let client = mock.client();
+
+    let err = client.wait_for_task(TASK, Duration::from_secs(1), |_| {}).await.unwrap_err();
+    assert_eq!(err.exit_code(), error::EXIT_FAILED);
+    assert_eq!(err.to_string(), "task JID_878700123456 ended Exception: Unable to transfer the image file.");
+
+    let err = client.wait_for_task("/redfish/v1/TaskService/Tasks/JID_878700654321", Duration::ZERO, |_| {}).await.unwrap_err();
+    assert_eq!(err.exit_code(), error::EXIT_TIMEOUT);
+    assert_eq!(err.to_string(), "task JID_878700123456 is still Running at 35% after 0 seconds");
+}
+
+#[tokio::test]
+async fn lists_firmware_with_component_types_and_install_states() {
+    let mock = MockIdrac::start().await;
+    let firmware = list_firmware(&[], &mock.client()).await.unwrap().firmware;
+
+    let kinds: Vec<(ComponentType, &str, Option<&str>)> = firmware.iter()
+        .map(|f| (f.component_type, f.version.as_deref().unwrap_or_default(), f.install_state.as_deref()))
+        .collect();
+    assert_eq!(kinds, vec![
+        (ComponentType::Bios, "2.17.1", Some("Installed")),
+        (ComponentType::Bios, "2.15.2", Some("Previous")),
+        (ComponentType::Idrac, "6.10.30.00", Some("Installed")),
+        (ComponentType::Raid, "25.5.9.0001", Some("Installed")),
+        (ComponentType::Nic, "21.85.21.92", Some("Installed")),
+        (ComponentType::Disk, "DL63", Some("Installed")),
+        (ComponentType::Psu, "00.1D.7D", Some("Installed")),
+        (ComponentType::Cpld, "1.0.6", Some("Installed")),
+        (ComponentType::Other, "4.35", Some("Installed")),
+    ]);
+    assert!(!firmware[1].is_installed());
+
+    let filtered = list_firmware(&[ComponentType::Raid, ComponentType::Disk], &mock.client()).await.unwrap().firmware;
+    let names: Vec<&str> = filtered.iter().filter_map(|f| f.name.as_deref()).collect();
+    assert_eq!(names, vec!["PERC H730P Mini", "Disk 0 in Backplane 1 of Integrated RAID Controller 1"]);
+}
+
+#[tokio::test]
+async fn firmware_without_dell_device_ids_is_classified_by_name() {
+    let mock = MockIdrac::start().await;
+    mock.serve("GET", "/redfish/v1/UpdateService/FirmwareInventory", 200, json!({
+        "Members": [{ "@odata.id": "/redfish/v1/UpdateService/FirmwareInventory/psu" }],
+    })).await;
+    mock.serve("GET", "/redfish/v1/UpdateService/FirmwareInventory/psu", 200, json!({
+        "Id": "psu", "Name": "Power Supply 1 Firmware", "Version": "1.2",
+    })).await;
+
+    let firmware = list_firmware(&[], &mock.client()).await.unwrap().firmware;
+    assert_eq!(firmware[0].component_type, ComponentType::Psu);
+    assert_eq!(firmware[0].install_state, None);
+    assert!(firmware[0].is_installed());
+}
+
+/// Makes the firmware inventory change after its first read, as if BIOS 2.18.1 had been
+/// installed, or staged when `state` is `Available`.
+async fn update_bios_inventory(mock: &MockIdrac, state: &str) {
+    let inventory = fixture("firmware_inventory");
+    mock.serve_once("GET", FIRMWARE, 200, inventory.clone()).await;
+
+    let mut members: Vec<Value> = inventory["Members"].as_array().unwrap().to_owned();
+    let mut image = |id: &str, version: &str| {
+        let mut bios = fixture("firmware_bios");
+        bios["@odata.id"] = json!(format!("{}/{}", FIRMWARE, id));
+        bios["Id"] = json!(id);
+        bios["Version"] = json!(version);
+        members.push(json!({ "@odata.id": bios["@odata.id"] }));
+        bios
+    };
+    let mut images = vec![image(&format!("{}-159-2.18.1", state), "2.18.1")];
+    if state == "Installed" {
+        images.push(image("Previous-159-2.17.1", "2.17.1"));
+    }
+    for bios in images {
+        mock.serve("GET", bios["@odata.id"].as_str().unwrap(), 200, bios.clone()).await;
+    }
+    if state == "Installed" {
+        members.retain(|m| !m["@odata.id"].as_str().unwrap().ends_with("-159-2.17.1") && !m["@odata.id"].as_str().unwrap().ends_with("-159-2.15.2"));
+    }
+    mock.serve("GET", FIRMWARE, 200, json!({ "@odata.id": FIRMWARE, "Members": members })).await;
+}
+
+#[tokio::test]
+async fn updates_firmware_from_a_local_package() {
+    let mock = MockIdrac::start().await;
+    update_bios_inventory(&mock, "Installed").await;
+    mock.respond("POST", "/redfish/v1/UpdateService/MultipartUpload", ResponseTemplate::new(202)
+        .insert_header("Location", TASK)).await;
+    let file = std::env::temp_dir().join(format!("BIOS_{}.EXE", mock.server.address().port()));
+    std::fs::write(&file, "dell update package").unwrap();
+
+    let mut states = Vec::new();
+    let update = update_firmware(&Image::File(file.clone()), UpdateApplyTime::Immediate, Duration::from_secs(60), |task| {
+        states.push(task.task_state.to_owned().unwrap_or_default())
+    }, &mock.client()).await.unwrap();
+    std::fs::remove_file(&file).unwrap();
+
+    assert_eq!(states, vec!["Completed"]);
+    let changes: Vec<(Option<&str>, Option<&str>, Option<&str>)> = update.changes.iter()
+        .map(|c| (c.before.as_deref(), c.after.as_deref(), c.install_state.as_deref()))
+        .collect();
+    assert_eq!(changes, vec![(Some("2.17.1"), Some("2.18.1"), Some("Installed"))]);
+    assert_eq!(update.changes[0].component_type, ComponentType::Bios);
+    assert!(!update.is_pending());
+
+    let requests = mock.server.received_requests().await.unwrap();
+    let upload = requests.iter().find(|r| r.method.to_string() == "POST" && r.url.path().ends_with("MultipartUpload")).unwrap();
+    let body = String::from_utf8_lossy(&upload.body);
+    assert!(body.contains(r#"{"@Redfish.OperationApplyTime":"Immediate","Targets":[]}"#), "{}", body);
+    assert!(body.contains(&format!("filename=\"{}\"", file.file_name().unwrap().to_string_lossy())), "{}", body);
+    assert!(body.contains("dell update package"));
+}
+
+#[tokio::test]
+async fn follows_firmware_updates_while_the_idrac_restarts() {
+    let mock = MockIdrac::start().await;
+    update_bios_inventory(&mock, "Installed").await;
+    mock.respond("POST", "/redfish/v1/UpdateService/Actions/UpdateService.SimpleUpdate", ResponseTemplate::new(202)
+        .insert_header("Location", TASK)).await;
+    // The web server answers 503 while the iDRAC starts, after which the session is gone.
+    mock.serve_once("GET", TASK, 503, json!({ "error": { "message": "Service Unavailable" } })).await;
+    mock.serve_once("GET", TASK, 401, json!({})).await;
+
+    let image = "https://repo.example.com/BIOS_2.18.1.EXE".parse().unwrap();
+    let update = update_firmware(&image, UpdateApplyTime::Immediate, Duration::from_secs(60), |_| {}, &mock.client()).await.unwrap();
+    assert_eq!(update.task.unwrap().task_state.as_deref(), Some("Completed"));
+    assert_eq!(update.changes.len(), 1);
+
+    let requests = mock.server.received_requests().await.unwrap();
+    let logins = requests.iter().filter(|r| r.method.to_string() == "POST" && r.url.path() == "/redfish/v1/SessionService/Sessions").count();
+    assert_eq!(logins, 2);
+}
+
+#[tokio::test]
+async fn gives_up_on_a_restarting_idrac_after_the_timeout() {
+    let mock = MockIdrac::start().await;
+    mock.respond("POST", "/redfish/v1/UpdateService/Actions/UpdateService.SimpleUpdate", ResponseTemplate::new(202)
+        .insert_header("Location", TASK)).await;
+    mock.serve("GET", TASK, 503, json!({ "error": { "message": "Service Unavailable" } })).await;
+
+    let image = "https://repo.example.com/BIOS_2.18.1.EXE".parse().unwrap();
+    let err = update_firmware(&image, UpdateApplyTime::Immediate, Duration::from_secs(1), |_| {}, &mock.client()).await.unwrap_err();
+    assert_eq!(err.exit_code(), error::EXIT_TIMEOUT);
+    assert!(err.to_string().contains("Service Unavailable"), "{}", err);
+}
+
+#[tokio::test]
+async fn stages_firmware_from_a_uri_until_the_next_reset() {
+    let mock = MockIdrac::start().await;
+    update_bios_inventory(&mock, "Available").await;
+    mock.respond("POST", "/redfish/v1/UpdateService/Actions/UpdateService.SimpleUpdate", ResponseTemplate::new(202)
+        .insert_header("Location", TASK)).await;
+    let mut task = fixture("task");
+    task["TaskState"] = json!("Running");
+    task["Oem"] = json!({ "Dell": { "JobState": "Scheduled" } });
+    mock.serve("GET", TASK, 200, task).await;
+
+    let image = "https://repo.example.com/BIOS_2.18.1.EXE".parse().unwrap();
+    let update = update_firmware(&image, UpdateApplyTime::OnReset, Duration::from_secs(60), |_| {}, &mock.client()).await.unwrap();
+    assert!(update.is_pending());
+    assert_eq!(update.changes.len(), 1);
+    assert_eq!(update.changes[0].before.as_deref(), Some("2.17.1"));
+    assert_eq!(update.changes[0].install_state.as_deref(), Some("Available"));
+
+    let requests = mock.server.received_requests().await.unwrap();
+    let action = requests.iter().find(|r| r.url.path().ends_with("UpdateService.SimpleUpdate")).unwrap();
+    assert_eq!(action.body_json::<Value>().unwrap(), json!({
+        "ImageURI": "https://repo.example.com/BIOS_2.18.1.EXE",
+        "TransferProtocol": "HTTPS",
+        "@Redfish.OperationApplyTime": "OnReset",
+    }));
+}
+
+#[tokio::test]
+async fn firmware_transfer_protocols_the_idrac_does_not_allow_are_refused() {
+    let mock = MockIdrac::start().await;
+    let image = "ftp://repo.example.com/BIOS_2.18.1.EXE".parse().unwrap();
+    let err = update_firmware(&image, UpdateApplyTime::Immediate, Duration::from_secs(60), |_| {}, &mock.client()).await.unwrap_err();
+    assert_eq!(err.exit_code(), error::EXIT_USAGE);
+    assert_eq!(err.to_string(), "transfer protocol FTP is not supported by this iDRAC, it allows: HTTP, NFS, CIFS, TFTP, HTTPS");
+
+    let missing = Image::File(std::env::temp_dir().join("idrac-cli-missing-package.EXE"));
+    let err = update_firmware(&missing, UpdateApplyTime::Immediate, Duration::from_secs(60), |_| {}, &mock.client()).await.unwrap_err();
+    assert_eq!(err.exit_code(), error::EXIT_IO);
+}
+
+const CATALOG: &str = r#"<?xml version="1.0" encoding="utf-16"?>
+<Manifest baseLocation="downloads.dell.com" dateTime="2026-09-30T08:15:42+05:30" version="26.09.00">
+  <SoftwareComponent path="FOLDER11111111M/1/BIOS_R640_2.18.1.EXE" vendorVersion="2.18.1" dellVersion="2.18.1">
+    <Name><Display lang="en"><![CDATA[Dell PowerEdge R640 BIOS]]></Display></Name>
+    <SupportedDevices><Device componentID="159" embedded="1"><Display lang="en">BIOS</Display></Device></SupportedDevices>
+    <SupportedSystems><Brand key="3" prefix="PE"><Model systemID="0716" systemIDType="BIOS"><Display>R640</Display></Model></Brand></SupportedSystems>
+  </SoftwareComponent>
+  <SoftwareComponent path="FOLDER22222222M/1/BIOS_R740_2.19.1.EXE" vendorVersion="2.19.1" dellVersion="2.19.1">
+    <Name><Display lang="en"><![CDATA[Dell PowerEdge R740 BIOS]]></Display></Name>
+    <SupportedDevices><Device componentID="159" embedded="1"><Display lang="en">BIOS</Display></Device></SupportedDevices>
+    <SupportedSystems><Brand key="3" prefix="PE"><Model systemID="08B1" systemIDType="BIOS"><Display>R740</Display></Model></Brand></SupportedSystems>
+  </SoftwareComponent>
+  <SoftwareComponent path="FOLDER33333333M/1/iDRAC_6.10.30.00.EXE" vendorVersion="6.10.30.00" dellVersion="A00">
+    <Name><Display lang="en"><![CDATA[iDRAC with Lifecycle Controller]]></Display></Name>
+    <SupportedDevices><Device componentID="25227" embedded="1"><Display lang="en">iDRAC</Display></Device></SupportedDevices>
+  </SoftwareComponent>
+  <SoftwareComponent path="FOLDER44444444M/1/SAS-RAID_Firmware_25.5.8.0001.EXE" vendorVersion="25.5.8.0001" dellVersion="A00">
+    <Name><Display lang="en"><![CDATA[PERC H730P Mini Firmware]]></Display></Name>
+    <SupportedDevices><Device componentID="101735" embedded="1"><Display lang="en">PERC H730P Mini</Display></Device></SupportedDevices>
+  </SoftwareComponent>
+  <SoftwareComponent path="FOLDER55555555M/1/Network_Firmware_22.00.EXE" vendorVersion="22.00.6" dellVersion="A00">
+    <Name><Display lang="en"><![CDATA[Broadcom NetXtreme Firmware]]></Display></Name>
+    <SupportedDevices><Device><PCIInfo deviceID="165F" vendorID="14E4" subDeviceID="1F5B" subVendorID="1028" /></Device></SupportedDevices>
+  </SoftwareComponent>
+</Manifest>
+"#;
+
+#[tokio::test]
+async fn checks_firmware_against_a_dell_catalog() {
+    let mock = MockIdrac::start().await;
+    let file = std::env::temp_dir().join(format!("idrac-cli-catalog-{}.xml", mock.server.address().port()));
+    // Dell publishes its catalogs in UTF-16.
+    let utf16: Vec<u8> = [0xFF, 0xFE].into_iter().chain(CATALOG.encode_utf16().flat_map(u16::to_le_bytes)).collect();
+    std::fs::write(&file, utf16).unwrap();
+    let catalog = Catalog::read(&file).unwrap();
+    std::fs::remove_file(&file).unwrap();
+    assert_eq!(catalog.packages.len(), 4);
+
+    let check = check_firmware(&catalog, &mock.client()).await.unwrap();
+    let results: Vec<(ComponentType, &str, Compliance)> = check.components.iter()
+        .map(|c| (c.component_type, c.catalog_version.as_str(), c.compliance))
+        .collect();
+    assert_eq!(results, vec![
+        (ComponentType::Bios, "2.18.1", Compliance::Outdated),
+        (ComponentType::Idrac, "6.10.30.00", Compliance::Current),
+        (ComponentType::Raid, "25.5.8.0001", Compliance::Newer),
+    ]);
+    assert_eq!(check.components[0].package.as_deref(), Some("https://downloads.dell.com/FOLDER11111111M/1/BIOS_R640_2.18.1.EXE"));
+
+    let err = check.check().unwrap_err();
+    assert_eq!(err.exit_code(), error::EXIT_DRIFT);
+    assert_eq!(err.to_string(), format!("1 component(s) are older than in {}", file.display()));
+}
+
+#[tokio::test]
+async fn firmware_manifests_match_components_by_id_or_name() {
+    let mock = MockIdrac::start().await;
+    let dir = std::env::temp_dir();
+    let manifest = dir.join(format!("idrac-cli-manifest-{}.yaml", mock.server.address().port()));
+    std::fs::write(&manifest, "\
+- component_id: 27763
+  version: '1.0.6'
+- name: power supply
+  version: '00.1D.7D'
+  package: nfs://10.0.0.5/dell/PSU_00.1D.7D.EXE
+").unwrap();
+    let catalog = Catalog::read(&manifest).unwrap();
+    let check = check_firmware(&catalog, &mock.client()).await.unwrap();
+    let types: Vec<ComponentType> = check.components.iter().map(|c| c.component_type).collect();
+    assert_eq!(types, vec![ComponentType::Psu, ComponentType::Cpld]);
+    assert!(check.check().is_ok());
+
+    std::fs::write(&manifest, "[{\"name\": \"iDRAC\", \"version\": 6.10}]").unwrap();
+    let err = Catalog::read(&manifest).unwrap_err();
+    std::fs::remove_file(&manifest).unwrap();
+    assert_eq!(err.to_string(), format!(
+        "invalid catalog {}: the version of entry 1 is not a string, quote versions such as '6.10'", manifest.display()
+    ));
+}
+
+#[test]
+fn firmware_versions_compare_segment_by_segment() {
+    assert_eq!(compare_versions("2.9.0", "2.10.1"), Ordering::Less);
+    assert_eq!(compare_versions("6.10.30.00", "6.10.30.00"), Ordering::Equal);
+    assert_eq!(compare_versions("DL6B", "DL63"), Ordering::Greater);
+    assert_eq!(compare_versions("1.0", "1.0.1"), Ordering::Less);
+}
+
+/// Serves the second and last page of the Lifecycle log.
+async fn serve_lclog_second_page(mock: &MockIdrac) {
+    mock.serve_query("GET", LCLOG_ENTRIES, ("$skip", "2"), 200, json!({
+        "@odata.id": LCLOG_ENTRIES,
+        "Members": [{
+            "Id": "5010",
+            "Created": "2026-10-17T23:59:02-05:00",
+            "Severity": "Warning",
+            "MessageId": "IDRAC.2.8.PDR1016",
+            "Message": "Drive 3 is removed from disk drive bay 1.",
+        }],
+    })).await;
+}
+
+fn ids(entries: &[LogEntry]) -> Vec<&str> {
+    entries.iter().map(|e| e.id.as_str()).collect()
+}
+
+#[tokio::test]
+async fn pages_through_log_entries() {
+    let mock = MockIdrac::start().await;
+    serve_lclog_second_page(&mock).await;
+    let client = mock.client();
+
+    let all = list_log_entries(Log::Lc, &LogFilter::default(), &client).await.unwrap().entries;
+    assert_eq!(ids(&all), vec!["5012", "5011", "5010"]);
+
+    let filter = LogFilter { limit: Some(2), ..Default::default() };
+    let first = list_log_entries(Log::Lc, &filter, &client).await.unwrap().entries;
+    assert_eq!(ids(&first), vec!["5012", "5011"]);
+    // Only the first read went on to the second page.
+    let pages = mock.server.received_requests().await.unwrap().iter()
+        .filter(|r| r.url.path() == LCLOG_ENTRIES && r.url.query() == Some("$skip=2"))
+        .count();
+    assert_eq!(pages, 1);
+}
+
+#[tokio::test]
+async fn filters_log_entries_by_severity_time_and_message_id() {
+    let mock = MockIdrac::start().await;
+    let client = mock.client();
+    let entries = |filter: LogFilter| {
+        let client = &client;
+        async move { list_log_entries(Log::Sel, &filter, client).await.unwrap().entries }
+    };
+
+    let severe = entries(LogFilter { severity: Some(Level::Warning), ..Default::default() }).await;
+    assert_eq!(ids(&severe), vec!["3", "2"]);
+
+    let day = entries(LogFilter {
+        since: Some(parse_time("2026-10-17T00:00:00-05:00").unwrap()),
+        until: Some(parse_time("2026-10-17T23:59:59-05:00").unwrap()),
+        ..Default::default()
+    }).await;
+    assert_eq!(ids(&day), vec!["2"]);
+
+    for wanted in ["IDRAC.2.8.PSU0003", "psu0003", "PSU*"] {
+        let power = entries(LogFilter { message_ids: vec![wanted.to_owned()], ..Default::default() }).await;
+        assert_eq!(ids(&power), vec!["3"], "{}", wanted);
+    }
+}
+
+#[test]
+fn log_times_are_absolute_local_or_relative() {
+    assert_eq!(parse_time("2026-10-18T08:30:00Z").unwrap().to_rfc3339(), "2026-10-18T08:30:00+00:00");
+    assert_eq!(parse_time("2026-10-18").unwrap().naive_local().to_string(), "2026-10-18 00:00:00");
+    assert_eq!(parse_time("2026-10-18 08:30").unwrap().naive_local().to_string(), "2026-10-18 08:30:00");
+
+    let ago = chrono::Local::now().fixed_offset() - parse_time("12h").unwrap();
+    assert!((ago.num_seconds() - 12 * 3600).abs() < 60);
+    assert_eq!(
+        parse_time("yesterday").unwrap_err(),
+        "'yesterday' is not a time, use e.g. 2026-10-18T08:30:00Z, 2026-10-18, 2026-10-18 08:30 or 12h"
+    );
+    assert!(parse_time("").is_err());
+}
+
+#[tokio::test]
+async fn clears_the_sel_but_not_the_lifecycle_log() {
+    let mock = MockIdrac::start().await;
+    let action = "/redfish/v1/Managers/iDRAC.Embedded.1/LogServices/Sel/Actions/LogService.ClearLog";
+    mock.serve("POST", action, 200, json!({})).await;
+    let client = mock.client();
+
+    assert_eq!(clear_log(Log::Sel, &client).await.unwrap().log, "System Event Log");
+    let requests = mock.server.received_requests().await.unwrap();
+    assert!(requests.iter().any(|r| r.method.to_string() == "POST" && r.url.path() == action));
+
+    let err = clear_log(Log::Lc, &client).await.unwrap_err();
+    assert_eq!(err.exit_code(), error::EXIT_USAGE);
+    assert_eq!(err.to_string(), "the Lifecycle log cannot be cleared on this iDRAC");
+}
+
+#[tokio::test]
+async fn self_signed_certificates_are_not_trusted_by_default() {
+    let mock = MockIdrac::start_tls().await;
+    let err = get_idrac_version(&mock.client()).await.unwrap_err();
+    assert!(matches!(err, Error::Tls { .. }), "{}", err);
+    assert_eq!(err.exit_code(), error::EXIT_TLS);
+
+    let mut settings = mock.settings();
+    settings.host = String::from("https://127.0.0.1:1");
+    let err = get_idrac_version(&RedfishClient::new(&settings).unwrap()).await.unwrap_err();
+    assert!(matches!(err, Error::Connection { .. }), "{}", err);
+}
+
+#[tokio::test]
+async fn pinned_fingerprints_are_accepted_in_any_notation() {
+    let mock = MockIdrac::start_tls().await;
+    let notations = [FINGERPRINT.to_owned(), FINGERPRINT.to_lowercase(), FINGERPRINT.replace(':', ""), FINGERPRINT.replace(':', " ")];
+    for fingerprint in notations {
+        let mut settings = mock.settings();
+        settings.fingerprint = Some(fingerprint.to_owned());
+        let client = RedfishClient::new(&settings).unwrap();
+        assert!(get_idrac_version(&client).await.is_ok(), "{}", fingerprint);
+    }
+}
+
+#[tokio::test]
+async fn certificates_not_matching_the_pinned_fingerprint_are_refused() {
+    let mock = MockIdrac::start_tls().await;
+    let mut settings = mock.settings();
+    settings.fingerprint = Some(FINGERPRINT.replace("71:3C", "00:00"));
+
+    let err = get_idrac_version(&RedfishClient::new(&settings).unwrap()).await.unwrap_err();
+    assert!(matches!(err, Error::Tls { .. }), "{}", err);
+    let requests = mock.server.received_requests().await.unwrap();
+    assert!(requests.is_empty(), "credentials were sent to an unverified iDRAC");
+}
+
+#[tokio::test]
+async fn certificates_issued_by_the_ca_bundle_are_trusted() {
+    let mock = MockIdrac::start_tls().await;
+    let mut settings = mock.settings();
+    settings.ca_bundle = Some(certs_dir().join("ca.pem"));
+    assert!(get_idrac_version(&RedfishClient::new(&settings).unwrap()).await.is_ok());
+
+    settings.ca_bundle = Some(fixtures_dir().join("system.json"));
+    let err = RedfishClient::new(&settings).unwrap_err();
+    assert_eq!(err.exit_code(), error::EXIT_CONFIG);
+    assert!(err.to_string().contains("no PEM certificates found"), "{}", err);
+}
+
+#[tokio::test]
+async fn unknown_names_are_reported_as_not_found() {
+    let mock = MockIdrac::start().await;
+    let client = mock.client();
+
+    let err = get_network_adapter("NIC.Slot.9", &client, false).await.unwrap_err();
+    assert_eq!(err.to_string(), "network adapter 'NIC.Slot.9' was not found");
+    assert_eq!(err.exit_code(), error::EXIT_NOT_FOUND);
+
+    let err = get_storage_controller("RAID.Slot.4-1", &client).await.unwrap_err();
+    assert_eq!(err.to_string(), "storage controller 'RAID.Slot.4-1' was not found");
+
+    let err = get_storage_disk("Disk.Bay.7", &client).await.unwrap_err();
+    assert_eq!(err.to_string(), "disk 'Disk.Bay.7' was not found");
+}
+
+#[tokio::test]
+async fn rejected_login_is_an_authentication_error() {
+    let mock = MockIdrac::start().await;
+    mock.serve("POST", "/redfish/v1/SessionService/Sessions", 401, json!({
+        "error": { "@Message.ExtendedInfo": [{ "Message": "Unable to complete the operation because an invalid username and/or password is entered" }] }
+    })).await;
+
+    let err = get_idrac_version(&mock.client()).await.unwrap_err();
+    assert!(matches!(err, Error::Unauthorized { status: 401 }));
+    assert_eq!(err.exit_code(), error::EXIT_AUTH);
+}
+
+#[tokio::test]
+async fn redfish_error_messages_are_surfaced() {
+    let mock = MockIdrac::start().await;
+    mock.serve("GET", "/redfish/v1/Managers/iDRAC.Embedded.1", 500, json!({
+        "error": {
+            "code": "Base.1.8.GeneralError",
+            "message": "A general error has occurred.",
+            "@Message.ExtendedInfo": [{
+                "Message": "Unable to run the method because the requested HTTP method is not allowed.",
+                "MessageId": "IDRAC.2.7.SYS402",
+                "Resolution": "Enter a valid method and retry the operation.",
+            }]
+        }
+    })).await;
+
+    let err = get_idrac_version(&mock.client()).await.unwrap_err();
+    assert_eq!(err.exit_code(), error::EXIT_REDFISH);
+    assert_eq!(
+        err.to_string(),
+        "iDRAC returned HTTP 500: Unable to run the method because the requested HTTP method is not allowed. (Enter a valid method and retry the operation.)"
+    );
+}
+
+#[tokio::test]
+async fn unexpected_payloads_are_schema_errors() {
+    let mock = MockIdrac::start().await;
+    let mut controller = fixture("raid_controller");
+    controller["StorageControllers"][0]["SpeedGbps"] = json!("fast");
+    mock.serve("GET", "/redfish/v1/Systems/System.Embedded.1/Storage/RAID.Integrated.1-1", 200, controller).await;
+
+    let err = get_storage_controller("RAID.Integrated.1-1", &mock.client()).await.unwrap_err();
+    assert!(matches!(err, Error::Schema { .. }));
+    assert_eq!(err.exit_code(), error::EXIT_SCHEMA);
+}
+
+#[tokio::test]
+async fn missing_and_null_properties_are_tolerated() {
+    let mock = MockIdrac::start().await;
+    let mut port = fixture("port_slot_1");
+    port.as_object_mut().unwrap().remove("WakeOnLANEnabled");
+    port["LinkStatus"] = Value::Null;
+    port["SupportedLinkCapabilities"][0]["LinkNetworkTechnology"] = json!("InfiniBand");
+    mock.serve("GET", "/redfish/v1/Chassis/System.Embedded.1/NetworkAdapters/NIC.Slot.3/NetworkPorts/NIC.Slot.3-1", 200, port).await;
+
+    let port = get_network_port("NIC.Slot.3", "NIC.Slot.3-1", &mock.client()).await.unwrap();
+    assert_eq!(port.wol_enabled, None);
+    assert_eq!(port.link_status, None);
+    assert_eq!(port.supported_link_capabilities[0].technology.as_deref(), Some("InfiniBand"));
+
+    let without = |name: &str, keys: &[&str]| {
+        let mut resource = fixture(name);
+        for key in keys {
+            resource.as_object_mut().unwrap().remove(*key);
+        }
+        resource
+    };
+    let storage = "/redfish/v1/Systems/System.Embedded.1/Storage";
+    mock.serve("GET", "/redfish/v1/Managers/iDRAC.Embedded.1", 200, without("manager", &["FirmwareVersion"])).await;
+    mock.serve("GET", "/redfish/v1/Chassis/System.Embedded.1/NetworkAdapters", 200, without("network_adapters", &["Name", "Members"])).await;
+    mock.serve("GET", storage, 200, without("storage", &["Name", "Members"])).await;
+    mock.serve("GET", &format!("{}/RAID.Integrated.1-1", storage), 200, without("raid_controller", &["Name"])).await;
+    mock.serve("GET", &format!("{}/RAID.Integrated.1-1/Volumes", storage), 200, without("raid_volumes", &["Members"])).await;
+    mock.serve("GET", &format!("{}/Volumes/Disk.Virtual.0:RAID.Integrated.1-1", storage), 200, without("volume", &["Name"])).await;
+
+    let client = mock.client();
+    assert_eq!(get_idrac_version(&client).await.unwrap().firmware_version, None);
+    assert!(list_network_adapters(&client).await.unwrap().ids.is_empty());
+    assert!(list_storage_controllers(&client).await.unwrap().ids.is_empty());
+    assert_eq!(get_storage_controller("RAID.Integrated.1-1", &client).await.unwrap().name, None);
+    assert!(list_storage_volumes("RAID.Integrated.1-1", &client).await.unwrap().ids.is_empty());
+    assert_eq!(get_storage_volume("Disk.Virtual.0:RAID.Integrated.1-1", &client).await.unwrap().name, None);
+}
+
+#[tokio::test]
+async fn oem_blocks_are_kept() {
+    let mock = MockIdrac::start().await;
+    let mut chassis = fixture("chassis");
+    chassis["Oem"] = json!({ "Dell": { "DellChassis": { "CanBeFRUed": true } } });
+    mock.serve("GET", "/redfish/v1/Chassis/System.Embedded.1", 200, chassis).await;
+
+    let chassis = get_chassis_info(&mock.client()).await.unwrap();
+    assert_eq!(chassis.oem.unwrap()["Dell"]["DellChassis"]["CanBeFRUed"], true);
+}
+
+#[tokio::test]
+async fn several_systems_need_a_selector() {
+    let mock = MockIdrac::start().await;
+    mock.serve("GET", "/redfish/v1/Systems", 200, json!({
+        "@odata.id": "/redfish/v1/Systems",
+        "Members": [
+            { "@odata.id": "/redfish/v1/Systems/System.Embedded.1" },
+            { "@odata.id": "/redfish/v1/Systems/System.Embedded.2" },
+        ]
+    })).await;
+
+    let err = get_chassis_info(&mock.client()).await.unwrap_err();
+    assert_eq!(err.exit_code(), error::EXIT_USAGE);
+
+    let mut settings = mock.settings();
+    settings.system = Some(String::from("System.Embedded.1"));
+    let chassis = get_chassis_info(&RedfishClient::new(&settings).unwrap()).await.unwrap();
+    assert_eq!(chassis.model.as_deref(), Some("PowerEdge R640"));
+}
+
+#[tokio::test]
+async fn session_is_deleted_on_close_without_cache() {
+    let mock = MockIdrac::start().await;
+    let client = mock.client();
+    get_idrac_version(&client).await.unwrap();
+    client.close().await.unwrap();
+
+    let requests = mock.server.received_requests().await.unwrap();
+    let logins = requests.iter().filter(|r| r.method.to_string() == "POST").count();
+    let logouts = requests.iter().filter(|r| r.method.to_string() == "DELETE" && r.url.path() == SESSION).count();
+    assert_eq!((logins, logouts), (1, 1));
+}
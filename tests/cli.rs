@@ -0,0 +1,913 @@
+mod common;
+
+use serde_json::{json, Value};
+use wiremock::ResponseTemplate;
+use common::{fixture, stderr, stdout, MockIdrac, FINGERPRINT, SESSION, TOKEN};
+
+#[tokio::test]
+async fn idrac_version() {
+    let mock = MockIdrac::start().await;
+    let output = mock.cli(&["idrac", "version"]).await;
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "iDRAC firmware version: 6.10.30.00\n");
+}
+
+#[tokio::test]
+async fn chassis_info() {
+    let mock = MockIdrac::start().await;
+    let output = mock.cli(&["chassis", "info"]).await;
+
+    assert_eq!(stdout(&output), "\
+Indicator LED: Off
+Type:          Dell Inc. PowerEdge R640
+Part number:   0X45NXA00
+Serial number: CN7475160G0187
+SKU:           7XQ5CH2
+Power state:   On
+Status:        OK
+");
+}
+
+#[tokio::test]
+async fn chassis_info_hides_status_when_not_enabled() {
+    let mock = MockIdrac::start().await;
+    let mut chassis = fixture("chassis");
+    chassis["Status"] = json!({ "Health": "Critical", "State": "StandbyOffline" });
+    mock.serve("GET", "/redfish/v1/Chassis/System.Embedded.1", 200, chassis).await;
+
+    let output = mock.cli(&["chassis", "info"]).await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(!stdout(&output).contains("Status:"));
+}
+
+#[tokio::test]
+async fn chassis_identify_off() {
+    let mock = MockIdrac::start().await;
+    mock.serve("PATCH", "/redfish/v1/Chassis/System.Embedded.1", 200, json!({})).await;
+
+    let output = mock.cli(&["chassis", "identify", "--off"]).await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "Indicator LED: Off\n");
+}
+
+#[tokio::test]
+async fn chassis_identify_needs_a_state() {
+    let mock = MockIdrac::start().await;
+
+    assert_eq!(mock.cli(&["chassis", "identify"]).await.status.code(), Some(2));
+    assert_eq!(mock.cli(&["chassis", "identify", "--on", "--off"]).await.status.code(), Some(2));
+    assert_eq!(mock.cli(&["chassis", "identify", "--off", "--duration", "5"]).await.status.code(), Some(2));
+}
+
+#[tokio::test]
+async fn chassis_info_as_json_yaml_and_csv() {
+    let mock = MockIdrac::start().await;
+
+    let json: Value = serde_json::from_str(&stdout(&mock.cli(&["chassis", "info", "--output", "json"]).await)).unwrap();
+    assert_eq!(json["SerialNumber"], "CN7475160G0187");
+    assert_eq!(json["Status"]["Health"], "OK");
+
+    let yaml = stdout(&mock.cli(&["chassis", "info", "-o", "yaml"]).await);
+    assert!(yaml.contains("SerialNumber: CN7475160G0187\n"));
+
+    let csv = stdout(&mock.cli(&["chassis", "info", "-o", "csv"]).await);
+    assert_eq!(csv, "\
+IndicatorLED,Manufacturer,Model,PartNumber,SerialNumber,SKU,PowerState,Health,State
+Off,Dell Inc.,PowerEdge R640,0X45NXA00,CN7475160G0187,7XQ5CH2,On,OK,Enabled
+");
+}
+
+#[tokio::test]
+async fn thermal_status() {
+    let mock = MockIdrac::start().await;
+    let output = mock.cli(&["thermal", "status"]).await;
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "\
+Temperatures:
+Sensor                   Reading  Warning above  Critical above  Status
+System Board Inlet Temp  24 °C    38 °C          42 °C           OK
+CPU1 Temp                87 °C    85 °C          90 °C           Warning
+CPU2 Temp                n/a      n/a            n/a             Absent
+
+Fans:
+Fan                 Reading   Warning below  Critical below  Redundancy  Status
+System Board Fan1A  6840 RPM  840            480             N+m         OK
+System Board Fan1B  360 RPM   840            480             N+m         Critical
+
+Redundancy: System Board Fan Redundancy N+m (Critical)
+");
+}
+
+#[tokio::test]
+async fn thermal_status_as_csv_has_levels() {
+    let mock = MockIdrac::start().await;
+    let csv = stdout(&mock.cli(&["thermal", "status", "-o", "csv"]).await);
+
+    let levels: Vec<&str> = csv.lines().skip(1).map(|line| line.rsplit(',').next().unwrap()).collect();
+    assert_eq!(levels, vec!["OK", "Warning", "OK", "OK", "Critical"]);
+}
+
+#[tokio::test]
+async fn network_list_network_adapters() {
+    let mock = MockIdrac::start().await;
+    let output = mock.cli(&["network", "list-network-adapters"]).await;
+
+    assert_eq!(stdout(&output), "Found 2 network adapter(s):\n- NIC.Integrated.1\n- NIC.Slot.3\n");
+}
+
+#[tokio::test]
+async fn network_list_network_adapters_when_empty() {
+    let mock = MockIdrac::start().await;
+    mock.serve("GET", "/redfish/v1/Chassis/System.Embedded.1/NetworkAdapters", 200, json!({ "Name": "Network Adapter Collection", "Members": [] })).await;
+
+    let output = mock.cli(&["network", "list-network-adapters"]).await;
+    assert_eq!(stdout(&output), "Found 0 network adapter(s):\n");
+    let output = mock.cli(&["network", "list-network-adapters", "-o", "json"]).await;
+    assert_eq!(stdout(&output), "[]\n");
+}
+
+#[tokio::test]
+async fn network_get_network_adapter() {
+    let mock = MockIdrac::start().await;
+    let output = mock.cli(&["network", "get-network-adapter", "--name", "NIC.Slot.3"]).await;
+
+    assert_eq!(stdout(&output), "\
+NIC:           NIC.Slot.3
+Manufacturer:  Intel Corporation
+Model:         Intel(R) Ethernet 10G 2P X710 Adapter
+Part number:   06VDPG
+Serial number: MYFLMIT003008L
+
+Found 1 ports on controller:
+- NIC.Slot.3-1
+");
+}
+
+#[tokio::test]
+async fn network_get_network_adapter_detailed() {
+    let mock = MockIdrac::start().await;
+    let output = mock.cli(&["network", "get-network-adapter", "--name", "NIC.Integrated.1", "--detailed"]).await;
+
+    let out = stdout(&output);
+    assert!(out.contains("- NIC.Integrated.1-2\n  Port number:    2\n  Link status:    Down\n"), "{}", out);
+
+    let csv = stdout(&mock.cli(&["network", "get-network-adapter", "--name", "NIC.Integrated.1", "--detailed", "-o", "csv"]).await);
+    assert_eq!(csv.lines().count(), 3);
+    assert!(csv.contains(",NIC.Integrated.1-1,1,Up,Ethernet,B0:26:28:1A:2B:3C,25000,true\n"), "{}", csv);
+}
+
+#[tokio::test]
+async fn network_get_network_port() {
+    let mock = MockIdrac::start().await;
+    let output = mock.cli(&["network", "get-network-port", "--adapter", "NIC.Slot.3", "--port", "NIC.Slot.3-1"]).await;
+
+    assert_eq!(stdout(&output), "\
+Port number:    1
+Link status:    Up
+Technology:     Ethernet
+Addresses:      3C:FD:FE:AA:BB:CC
+Link speed:     25000 Mbps (Ethernet)
+Capabilities:   WakeOnLAN
+Wake on LAN:    false
+");
+}
+
+#[tokio::test]
+async fn power_status() {
+    let mock = MockIdrac::start().await;
+    let output = mock.cli(&["power", "status"]).await;
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "\
+System Power Control:
+  Consumed:       238 W
+  Minimum:        210 W over the last 60 min
+  Maximum:        412 W over the last 60 min
+  Average:        241 W over the last 60 min
+  Capacity:       1512 W
+  Power cap:      disabled
+Redundancy:       PSU Redundancy N+m (Critical)
+Found 2 power supplies:
+- PSU.Slot.1
+  Model:          PWR SPLY,750W,RDNT,DELTA
+  Firmware:       00.1D.7D
+  Input voltage:  230 V
+  Capacity:       750 W
+  Redundancy:     N+m
+  Status:         OK
+- PSU.Slot.2
+  Model:          PWR SPLY,750W,RDNT,DELTA
+  Firmware:       00.1D.7D
+  Input voltage:  n/a
+  Capacity:       750 W
+  Redundancy:     N+m
+  Status:         Critical
+");
+}
+
+#[tokio::test]
+async fn power_status_as_csv() {
+    let mock = MockIdrac::start().await;
+    let csv = stdout(&mock.cli(&["power", "status", "-o", "csv"]).await);
+
+    assert_eq!(csv, "\
+PowerConsumedWatts,MinConsumedWatts,MaxConsumedWatts,AverageConsumedWatts,LimitInWatts,PowerSupply,Model,FirmwareVersion,LineInputVoltage,PowerCapacityWatts,Redundancy,Health
+238,210,412,241,n/a,PSU.Slot.1,\"PWR SPLY,750W,RDNT,DELTA\",00.1D.7D,230,750,N+m,OK
+238,210,412,241,n/a,PSU.Slot.2,\"PWR SPLY,750W,RDNT,DELTA\",00.1D.7D,n/a,750,N+m,Critical
+");
+}
+
+#[tokio::test]
+async fn power_cap_show() {
+    let mock = MockIdrac::start().await;
+    let mut power = fixture("power");
+    power["PowerControl"][0]["PowerLimit"] = json!({ "LimitInWatts": 500, "LimitException": "LogEventOnly", "CorrectionInMs": 6000 });
+    mock.serve("GET", "/redfish/v1/Chassis/System.Embedded.1/Power", 200, power).await;
+
+    let output = mock.cli(&["power", "cap", "show"]).await;
+    assert_eq!(stdout(&output), "\
+Power cap:  500 W
+Exception:  LogEventOnly
+Correction: 6000 ms
+Consumed:   238 W
+Capacity:   1512 W
+");
+}
+
+#[tokio::test]
+async fn power_cap_clear() {
+    let mock = MockIdrac::start().await;
+    mock.serve("PATCH", "/redfish/v1/Chassis/System.Embedded.1/Power", 204, json!({})).await;
+
+    let output = mock.cli(&["power", "cap", "clear", "-o", "json"]).await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    let cap: Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(cap["LimitInWatts"], Value::Null);
+
+    let requests = mock.server.received_requests().await.unwrap();
+    let patch = requests.iter().find(|r| r.method.to_string() == "PATCH").unwrap();
+    assert_eq!(patch.body_json::<Value>().unwrap(), json!({ "PowerControl": [{ "PowerLimit": { "LimitInWatts": null } }] }));
+}
+
+#[tokio::test]
+async fn power_list_reset_types() {
+    let mock = MockIdrac::start().await;
+    let output = mock.cli(&["power", "list-reset-types", "-o", "json"]).await;
+
+    let types: Vec<String> = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(types.len(), 8);
+}
+
+#[tokio::test]
+async fn power_off_and_wait() {
+    let mock = MockIdrac::start().await;
+    let mut system = fixture("system");
+    system["PowerState"] = json!("Off");
+    mock.serve("GET", "/redfish/v1/Systems/System.Embedded.1", 200, system).await;
+    mock.serve("POST", "/redfish/v1/Systems/System.Embedded.1/Actions/ComputerSystem.Reset", 204, json!({})).await;
+
+    let output = mock.cli(&["power", "off", "--wait"]).await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "Requested ForceOff reset\nPower state: Off\n");
+}
+
+#[tokio::test]
+async fn power_shutdown_times_out() {
+    let mock = MockIdrac::start().await;
+    mock.serve("POST", "/redfish/v1/Systems/System.Embedded.1/Actions/ComputerSystem.Reset", 204, json!({})).await;
+
+    let output = mock.cli(&["power", "shutdown", "--wait", "--timeout", "0"]).await;
+    assert_eq!(output.status.code(), Some(10));
+    assert_eq!(stderr(&output), "Error: power state is still On after 0 seconds, expected Off\n");
+}
+
+#[tokio::test]
+async fn storage_list_storage_controllers() {
+    let mock = MockIdrac::start().await;
+    let output = mock.cli(&["storage", "list-storage-controllers"]).await;
+
+    assert_eq!(stdout(&output), "Found 2 storage controller(s):\n- RAID.Integrated.1-1\n- AHCI.Embedded.1-1\n");
+}
+
+#[tokio::test]
+async fn storage_get_storage_controller() {
+    let mock = MockIdrac::start().await;
+    let output = mock.cli(&["storage", "get-storage-controller", "--name", "RAID.Integrated.1-1"]).await;
+
+    let out = stdout(&output);
+    assert!(out.starts_with("Device name: PERC H730P Mini\nStorage controller:\n"), "{}", out);
+    assert!(out.contains("  Speed:            12 Gbps\n"));
+    assert!(out.ends_with("Attached drives:\n- Disk.Bay.0:Enclosure.Internal.0-1:RAID.Integrated.1-1\n- Disk.Bay.1:Enclosure.Internal.0-1:RAID.Integrated.1-1\n"));
+}
+
+#[tokio::test]
+async fn storage_list_storage_volumes() {
+    let mock = MockIdrac::start().await;
+    let output = mock.cli(&["storage", "list-storage-volumes", "--controller", "RAID.Integrated.1-1"]).await;
+
+    assert_eq!(stdout(&output), "Found 1 storage volume(s):\n- Disk.Virtual.0:RAID.Integrated.1-1\n");
+}
+
+#[tokio::test]
+async fn storage_get_storage_volume() {
+    let mock = MockIdrac::start().await;
+    let output = mock.cli(&["storage", "get-storage-volume", "--name", "Disk.Virtual.0:RAID.Integrated.1-1"]).await;
+
+    assert_eq!(stdout(&output), "\
+Volume name: os
+Description: os
+Block size:  512 bytes
+Capacity:    479.56 GB
+Encrypted:   false
+Status:      OK
+Drives:
+- Disk.Bay.0:Enclosure.Internal.0-1:RAID.Integrated.1-1
+- Disk.Bay.1:Enclosure.Internal.0-1:RAID.Integrated.1-1
+");
+}
+
+#[tokio::test]
+async fn storage_get_storage_disk() {
+    let mock = MockIdrac::start().await;
+    let output = mock.cli(&["storage", "get-storage-disk", "--name", "Disk.Bay.0:Enclosure.Internal.0-1:RAID.Integrated.1-1"]).await;
+
+    let out = stdout(&output);
+    assert!(out.contains("Type:              TOSHIBA AL15SEB060N\n"), "{}", out);
+    assert!(out.contains("Rotation speed:    10000 RPM\n"));
+}
+
+#[tokio::test]
+async fn storage_get_storage_disk_without_rotation_speed() {
+    let mock = MockIdrac::start().await;
+    let mut disk = fixture("drive_bay0");
+    disk["MediaType"] = json!("SSD");
+    disk["RotationSpeedRPM"] = Value::Null;
+    mock.serve("GET", "/redfish/v1/Systems/System.Embedded.1/Storage/Drives/Disk.Bay.0:Enclosure.Internal.0-1:RAID.Integrated.1-1", 200, disk).await;
+
+    let output = mock.cli(&["storage", "get-storage-disk", "--name", "Disk.Bay.0:Enclosure.Internal.0-1:RAID.Integrated.1-1"]).await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("Rotation speed:    n/a\n"));
+}
+
+#[tokio::test]
+async fn network_get_network_port_without_optional_properties() {
+    let mock = MockIdrac::start().await;
+    mock.serve("GET", "/redfish/v1/Chassis/System.Embedded.1/NetworkAdapters/NIC.Slot.3/NetworkPorts/NIC.Slot.3-1", 200, json!({
+        "@odata.id": "/redfish/v1/Chassis/System.Embedded.1/NetworkAdapters/NIC.Slot.3/NetworkPorts/NIC.Slot.3-1",
+        "Id": "NIC.Slot.3-1",
+        "PhysicalPortNumber": "1",
+        "LinkStatus": null,
+    })).await;
+
+    let output = mock.cli(&["network", "get-network-port", "--adapter", "NIC.Slot.3", "--port", "NIC.Slot.3-1"]).await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "\
+Port number:    1
+Link status:    n/a
+Technology:     n/a
+Addresses:      
+Capabilities:   
+Wake on LAN:    n/a
+");
+}
+
+#[tokio::test]
+async fn bios_get() {
+    let mock = MockIdrac::start().await;
+    let output = mock.cli(&["bios", "get", "--name", "SysProfile"]).await;
+
+    assert_eq!(stdout(&output), "\
+Name:           SysProfile
+Description:    System Profile
+Current value:  PerfPerWattOptimizedDapc
+Pending value:  PerfOptimized
+Type:           Enumeration
+Allowed values: PerfPerWattOptimizedDapc, PerfOptimized, Custom
+Read-only:      false
+Help:           Sets the System Profile to Performance Per Watt (DAPC), Performance, or Custom mode.
+");
+}
+
+#[tokio::test]
+async fn bios_pending() {
+    let mock = MockIdrac::start().await;
+    let output = mock.cli(&["bios", "pending", "-o", "csv"]).await;
+
+    assert_eq!(stdout(&output), "\
+Name,CurrentValue,PendingValue,AllowedValues,DisplayName
+SysProfile,PerfPerWattOptimizedDapc,PerfOptimized,PerfPerWattOptimizedDapc PerfOptimized Custom,System Profile
+");
+}
+
+#[tokio::test]
+async fn bios_set_on_next_reboot() {
+    let mock = MockIdrac::start().await;
+    mock.respond("PATCH", "/redfish/v1/Systems/System.Embedded.1/Bios/Settings", ResponseTemplate::new(202)
+        .insert_header("Location", "/redfish/v1/Managers/iDRAC.Embedded.1/Jobs/JID_878632532911")).await;
+
+    let output = mock.cli(&["bios", "set", "LogicalProc=Disabled", "BootMode=Bios"]).await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "\
+Name         Current  Pending
+LogicalProc  Enabled  Disabled
+BootMode     Uefi     Bios
+Applies on the next reboot
+Job: JID_878632532911
+");
+}
+
+#[tokio::test]
+async fn bios_set_rejects_invalid_values() {
+    let mock = MockIdrac::start().await;
+    let output = mock.cli(&["bios", "set", "SysProfile=Fast"]).await;
+
+    assert_eq!(output.status.code(), Some(2));
+    assert_eq!(stderr(&output), "Error: invalid value 'Fast' for BIOS attribute SysProfile: expected one of PerfPerWattOptimizedDapc, PerfOptimized, Custom\n");
+
+    let output = mock.cli(&["bios", "set", "LogicalProc"]).await;
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[tokio::test]
+async fn bios_diff_against_another_profile() {
+    let mock = MockIdrac::start().await;
+    let reference = MockIdrac::start().await;
+    let mut bios = fixture("bios");
+    bios["Attributes"]["LogicalProc"] = json!("Disabled");
+    bios["Attributes"]["AssetTag"] = json!("rack12");
+    reference.serve("GET", "/redfish/v1/Systems/System.Embedded.1/Bios", 200, bios).await;
+
+    let settings = format!("\n[hosts.reference]\nhost = \"{}\"\n", reference.uri());
+    let output = mock.cli_with_settings(&settings, &["bios", "diff", "--against", "reference", "--ignore", "AssetTag"]).await;
+    assert_eq!(output.status.code(), Some(11));
+    assert_eq!(stdout(&output), "\
+Name         Change   Baseline  Current
+LogicalProc  Changed  Disabled  Enabled
+");
+    assert_eq!(stderr(&output), "Error: 1 BIOS attribute(s) differ from reference\n");
+}
+
+#[tokio::test]
+async fn bios_diff_against_saved_list() {
+    let mock = MockIdrac::start().await;
+    let saved = mock.cli(&["bios", "list", "-o", "yaml"]).await;
+    let file = std::env::temp_dir().join(format!("idrac-cli-bios-{}.yaml", mock.server.address().port()));
+    std::fs::write(&file, &saved.stdout).unwrap();
+
+    let output = mock.cli(&["bios", "diff", "--baseline", file.to_str().unwrap()]).await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), format!("No differences from {}\n", file.display()));
+}
+
+#[tokio::test]
+async fn boot_show() {
+    let mock = MockIdrac::start().await;
+    let output = mock.cli(&["boot", "show"]).await;
+
+    assert_eq!(stdout(&output), "\
+Boot mode:       UEFI
+Boot override:   none
+Allowed targets: None, Pxe, Floppy, Cd, Hdd, BiosSetup, Utilities, UefiTarget, SDCard, UefiHttp
+Boot order:
+  1. Boot0001  Red Hat Enterprise Linux
+  2. Boot0002  PXE Device 1: Integrated NIC 1 Port 1 Partition 1 (disabled)
+  3. Boot0000  Embedded SATA Port Optical Drive E: HL-DT-ST DVD-ROM DU90N
+");
+}
+
+#[tokio::test]
+async fn boot_once_pxe() {
+    let mock = MockIdrac::start().await;
+    let mut system = fixture("system");
+    system["Boot"]["BootSourceOverrideEnabled"] = json!("Once");
+    system["Boot"]["BootSourceOverrideTarget"] = json!("Pxe");
+    mock.serve("GET", "/redfish/v1/Systems/System.Embedded.1", 200, system).await;
+    mock.serve("PATCH", "/redfish/v1/Systems/System.Embedded.1", 200, json!({})).await;
+
+    let output = mock.cli(&["boot", "once", "pxe", "-o", "csv"]).await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "\
+Position,BootOptionReference,DisplayName,BootOptionEnabled,BootSourceOverrideTarget,BootSourceOverrideEnabled,BootSourceOverrideMode
+1,Boot0001,Red Hat Enterprise Linux,true,Pxe,Once,UEFI
+2,Boot0002,PXE Device 1: Integrated NIC 1 Port 1 Partition 1,false,Pxe,Once,UEFI
+3,Boot0000,Embedded SATA Port Optical Drive E: HL-DT-ST DVD-ROM DU90N,true,Pxe,Once,UEFI
+");
+}
+
+#[tokio::test]
+async fn bios_set_and_wait_for_the_job() {
+    let mock = MockIdrac::start().await;
+    mock.respond("PATCH", "/redfish/v1/Systems/System.Embedded.1/Bios/Settings", ResponseTemplate::new(202)
+        .insert_header("Location", "/redfish/v1/Managers/iDRAC.Embedded.1/Jobs/JID_878632532911")).await;
+
+    let output = mock.cli(&["bios", "set", "LogicalProc=Disabled", "--apply", "immediate", "--wait"]).await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).ends_with("Applying now, the system reboots\nJob: JID_878632532911 (Completed)\n"), "{}", stdout(&output));
+    assert_eq!(stderr(&output), "100% Completed: Job completed successfully.\n");
+}
+
+#[tokio::test]
+async fn bios_set_on_reset_waits_until_the_job_is_scheduled() {
+    let mock = MockIdrac::start().await;
+    mock.respond("PATCH", "/redfish/v1/Systems/System.Embedded.1/Bios/Settings", ResponseTemplate::new(202)
+        .insert_header("Location", "/redfish/v1/Managers/iDRAC.Embedded.1/Jobs/JID_878659984891")).await;
+
+    let output = mock.cli(&["bios", "set", "LogicalProc=Disabled", "--wait", "--timeout", "60"]).await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).ends_with("Applies on the next reboot\nJob: JID_878659984891 (Scheduled)\n"), "{}", stdout(&output));
+}
+
+#[tokio::test]
+async fn boot_order_and_wait_for_the_job() {
+    let mock = MockIdrac::start().await;
+    mock.respond("PATCH", "/redfish/v1/Systems/System.Embedded.1", ResponseTemplate::new(202)
+        .insert_header("Location", "/redfish/v1/Managers/iDRAC.Embedded.1/Jobs/JID_878632532911")).await;
+
+    let output = mock.cli(&["boot", "order", "Boot0002", "--wait"]).await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("Boot order:\n  1. Boot0002"), "{}", stdout(&output));
+    assert!(stdout(&output).ends_with("Job: JID_878632532911 (Completed)\n"), "{}", stdout(&output));
+    assert_eq!(stderr(&output), "100% Completed: Job completed successfully.\n");
+
+    let output = mock.cli(&["boot", "order", "Boot0002", "--timeout", "60"]).await;
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[tokio::test]
+async fn jobs_list() {
+    let mock = MockIdrac::start().await;
+    let output = mock.cli(&["jobs", "list"]).await;
+
+    assert_eq!(stdout(&output), "\
+Id                Name                       State      Percent  Message
+JID_878632532911  Configure: BIOS.Setup.1-1  Completed  100      Job completed successfully.
+JID_878659984891  Firmware Update: BIOS      Scheduled  0        Task successfully scheduled.
+");
+}
+
+#[tokio::test]
+async fn jobs_wait_for_failed_job() {
+    let mock = MockIdrac::start().await;
+    let mut job = fixture("job_firmware");
+    job["JobState"] = json!("CompletedWithErrors");
+    job["Message"] = json!("Some components could not be updated.");
+    mock.serve("GET", "/redfish/v1/Managers/iDRAC.Embedded.1/Jobs/JID_878659984891", 200, job).await;
+
+    let output = mock.cli(&["jobs", "wait", "JID_878659984891"]).await;
+    assert_eq!(output.status.code(), Some(12));
+    assert_eq!(stderr(&output), "\
+  0% CompletedWithErrors: Some components could not be updated.
+Error: job JID_878659984891 completed with errors: Some components could not be updated.
+");
+}
+
+#[tokio::test]
+async fn jobs_delete() {
+    let mock = MockIdrac::start().await;
+    mock.serve("DELETE", "/redfish/v1/Managers/iDRAC.Embedded.1/Jobs/JID_878659984891", 200, json!({})).await;
+
+    let output = mock.cli(&["jobs", "delete", "JID_878659984891"]).await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "Deleted 1 job(s)\n- JID_878659984891\n");
+}
+
+#[tokio::test]
+async fn task_watch() {
+    let mock = MockIdrac::start().await;
+    let output = mock.cli(&["task", "watch", "/redfish/v1/TaskService/Tasks/JID_878700123456"]).await;
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stderr(&output), "100% Completed: The specified job has completed successfully.\n");
+    assert_eq!(stdout(&output), "\
+Task:     JID_878700123456
+Name:     Firmware Update: iDRAC with Lifecycle Controller
+State:    Completed
+Status:   OK
+Progress: 100%
+Start:    2026-10-18T08:02:11-05:00
+End:      2026-10-18T08:09:40-05:00
+Messages:
+  - Task successfully scheduled.
+  - The specified job has completed successfully.
+");
+}
+
+#[tokio::test]
+async fn firmware_list() {
+    let mock = MockIdrac::start().await;
+    let output = mock.cli(&["firmware", "list", "--type", "bios,cpld"]).await;
+
+    assert_eq!(stdout(&output), "\
+Type  Name         Version  Updateable  State      Released
+BIOS  BIOS         2.17.1   true        Installed  2022-11-22
+BIOS  BIOS         2.15.2   true        Previous   2022-06-30
+CPLD  System CPLD  1.0.6    true        Installed  2020-09-18
+");
+}
+
+#[tokio::test]
+async fn firmware_update_from_uri() {
+    let mock = MockIdrac::start().await;
+    mock.respond("POST", "/redfish/v1/UpdateService/Actions/UpdateService.SimpleUpdate", ResponseTemplate::new(202)
+        .insert_header("Location", "/redfish/v1/TaskService/Tasks/JID_878700123456")).await;
+    let output = mock.cli(&["firmware", "update", "http://repo.example.com/iDRAC_6.10.30.00.EXE"]).await;
+
+    assert_eq!(output.status.code(), Some(0), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "\
+Task:  JID_878700123456 (Completed)
+Image: http://repo.example.com/iDRAC_6.10.30.00.EXE
+No firmware version changed
+");
+}
+
+#[tokio::test]
+async fn firmware_update_that_fails() {
+    let mock = MockIdrac::start().await;
+    mock.respond("POST", "/redfish/v1/UpdateService/Actions/UpdateService.SimpleUpdate", ResponseTemplate::new(202)
+        .insert_header("Location", "/redfish/v1/TaskService/Tasks/JID_878700123456")).await;
+    let mut task = fixture("task");
+    task["TaskState"] = json!("Exception");
+    task["Messages"] = json!([{ "Message": "Unable to transfer the image file." }]);
+    mock.serve("GET", "/redfish/v1/TaskService/Tasks/JID_878700123456", 200, task).await;
+
+    let output = mock.cli(&["firmware", "update", "nfs://10.0.0.5/share/BIOS.EXE", "--apply", "on-reset"]).await;
+    assert_eq!(output.status.code(), Some(12));
+    assert!(stderr(&output).ends_with("Error: task JID_878700123456 ended Exception: Unable to transfer the image file.\n"));
+}
+
+#[tokio::test]
+async fn firmware_check_with_outdated_components() {
+    let mock = MockIdrac::start().await;
+    let manifest = std::env::temp_dir().join(format!("idrac-cli-manifest-{}.json", mock.server.address().port()));
+    std::fs::write(&manifest, json!([
+        { "component_id": "159", "version": "2.18.1", "package": "https://repo.example.com/BIOS_2.18.1.EXE" },
+        { "name": "System CPLD", "version": "1.0.6" },
+    ]).to_string()).unwrap();
+
+    let output = mock.cli(&["firmware", "check", "--catalog", manifest.to_str().unwrap()]).await;
+    std::fs::remove_file(&manifest).unwrap();
+    assert_eq!(output.status.code(), Some(11));
+    assert_eq!(stdout(&output), "\
+Type  Name  Installed  Catalog  Package
+BIOS  BIOS  2.17.1     2.18.1   https://repo.example.com/BIOS_2.18.1.EXE
+");
+    assert_eq!(stderr(&output), format!("Error: 1 component(s) are older than in {}\n", manifest.display()));
+}
+
+#[tokio::test]
+async fn logs_list_severe_entries() {
+    let mock = MockIdrac::start().await;
+    let output = mock.cli(&["logs", "list", "sel", "--severity", "warning", "--since", "2026-10-01"]).await;
+
+    assert_eq!(stdout(&output), "\
+Created                    Severity  MessageId          Message
+2026-10-18T07:41:03-05:00  Critical  IDRAC.2.8.PSU0003  The power input for power supply 2 is lost.
+2026-10-17T22:15:40-05:00  Warning   IDRAC.2.8.FAN0001  Fan 4 RPM is less than the lower warning threshold.
+");
+}
+
+#[tokio::test]
+async fn logs_clear_sel() {
+    let mock = MockIdrac::start().await;
+    mock.serve("POST", "/redfish/v1/Managers/iDRAC.Embedded.1/LogServices/Sel/Actions/LogService.ClearLog", 200, json!({})).await;
+
+    let output = mock.cli(&["logs", "clear", "sel"]).await;
+    assert_eq!(stdout(&output), "Cleared the System Event Log\n");
+
+    let output = mock.cli(&["logs", "list", "lc", "--since", "last week"]).await;
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("'last week' is not a time"));
+}
+
+#[tokio::test]
+async fn trusts_the_first_certificate_seen_and_pins_it() {
+    let mock = MockIdrac::start_tls().await;
+    let known_hosts = mock.dir().join("idrac-cli").join("known_hosts");
+    let _ = std::fs::remove_file(&known_hosts);
+    assert_eq!(mock.cli(&["idrac", "version"]).await.status.code(), Some(5));
+
+    let recorded = format!("{} {}\n", mock.uri(), FINGERPRINT.replace(':', "").to_lowercase());
+    for _ in 0..2 {
+        let output = mock.cli_with_settings("trust_on_first_use = true\n", &["idrac", "version"]).await;
+        assert!(output.status.success(), "{}", stderr(&output));
+        assert_eq!(std::fs::read_to_string(&known_hosts).unwrap(), recorded);
+    }
+
+    std::fs::write(&known_hosts, format!("{} {}\n", mock.uri(), "00".repeat(32))).unwrap();
+    let output = mock.cli_with_settings("trust_on_first_use = true\n", &["idrac", "version"]).await;
+    assert_eq!(output.status.code(), Some(5), "{}", stderr(&output));
+}
+
+#[cfg(unix)]
+fn chmod(file: &std::path::Path, mode: u32) {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(file, std::fs::Permissions::from_mode(mode)).unwrap();
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn passwords_in_world_readable_settings_are_refused() {
+    let mock = MockIdrac::start().await;
+    let mut command = mock.command("password = \"calvin\"\n");
+    chmod(&mock.settings_file(), 0o644);
+    let output = command.env_remove("IDRAC_PASSWORD").args(["idrac", "version"]).output().await.unwrap();
+    assert_eq!(output.status.code(), Some(3));
+    assert!(stderr(&output).contains("world-readable"), "{}", stderr(&output));
+    assert!(mock.login_passwords().await.is_empty());
+
+    chmod(&mock.settings_file(), 0o640);
+    let output = command.output().await.unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(mock.login_passwords().await, vec!["calvin"]);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn password_files_accessible_by_others_are_refused() {
+    let mock = MockIdrac::start().await;
+    let file = mock.dir().join("password");
+    std::fs::create_dir_all(mock.dir()).unwrap();
+    std::fs::write(&file, "from-file\n").unwrap();
+    let mut command = mock.command(&format!("password_file = \"{}\"\n", file.display()));
+    command.env_remove("IDRAC_PASSWORD").args(["idrac", "version"]);
+
+    for mode in [0o640, 0o604, 0o610] {
+        chmod(&file, mode);
+        let output = command.output().await.unwrap();
+        assert_eq!(output.status.code(), Some(3), "mode {:o}", mode);
+        assert!(stderr(&output).contains("must not be accessible by group or others"), "{}", stderr(&output));
+    }
+    assert!(mock.login_passwords().await.is_empty());
+
+    chmod(&file, 0o600);
+    let output = command.output().await.unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(mock.login_passwords().await, vec!["from-file"]);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn password_sources_are_tried_in_order() {
+    let mock = MockIdrac::start().await;
+    let file = mock.dir().join("password");
+    std::fs::create_dir_all(mock.dir()).unwrap();
+    std::fs::write(&file, "from-file\n").unwrap();
+    chmod(&file, 0o600);
+    let env = "password_env = \"IDRAC_TEST_PASSWORD\"\n";
+    let file = format!("password_file = \"{}\"\n", file.display());
+    let command = "password_command = \"echo from-command\"\n";
+
+    let run = |extra: String, password: Option<&'static str>| {
+        let mut cli = mock.command(&extra);
+        cli.env_remove("IDRAC_PASSWORD").env("IDRAC_TEST_PASSWORD", "from-env").args(["idrac", "version"]);
+        if let Some(password) = password {
+            cli.env("IDRAC_PASSWORD", password);
+        }
+        async move { cli.output().await.unwrap() }
+    };
+    for (extra, password) in [
+        (format!("{}{}{}", env, file, command), Some("from-variable")),
+        (format!("{}{}{}", env, file, command), None),
+        (format!("{}{}", file, command), None),
+        (command.to_owned(), None),
+    ] {
+        let output = run(extra, password).await;
+        assert!(output.status.success(), "{}", stderr(&output));
+    }
+    assert_eq!(mock.login_passwords().await, vec!["from-variable", "from-env", "from-file", "from-command"]);
+
+    let output = run(String::from("password_env = \"IDRAC_TEST_UNSET\"\n"), None).await;
+    assert_eq!(output.status.code(), Some(3));
+    assert!(stderr(&output).contains("IDRAC_TEST_UNSET"), "{}", stderr(&output));
+    let output = run(String::from("password_command = \"exit 1\"\n"), None).await;
+    assert_eq!(output.status.code(), Some(3));
+    assert_eq!(mock.login_passwords().await.len(), 4);
+}
+
+#[tokio::test]
+async fn one_session_serves_the_whole_invocation() {
+    let mock = MockIdrac::start().await;
+    let output = mock.cli(&["firmware", "list"]).await;
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let requests = mock.server.received_requests().await.unwrap();
+    let gets: Vec<_> = requests.iter().filter(|r| r.method.to_string() == "GET").collect();
+    assert!(gets.len() > 5);
+    assert!(gets.iter().all(|r| r.headers.get(&"X-Auth-Token".parse().unwrap()).map(|t| t == TOKEN).unwrap_or(false)));
+    assert_eq!(mock.login_users().await.len(), 1);
+    // Without the session cache, the session is deleted on exit.
+    assert_eq!(requests.iter().filter(|r| r.method.to_string() == "DELETE" && r.url.path() == SESSION).count(), 1);
+}
+
+#[tokio::test]
+async fn sessions_are_cached_across_invocations() {
+    let mock = MockIdrac::start_tls().await;
+    let _ = std::fs::remove_dir_all(mock.dir().join("idrac-cli").join("sessions"));
+    let cached = "\n[hosts.cached]\nsession_cache = true\n";
+    let args = ["--profile", "cached", "--insecure", "idrac", "version"];
+
+    for _ in 0..2 {
+        let output = mock.cli_with_settings(cached, &args).await;
+        assert!(output.status.success(), "{}", stderr(&output));
+    }
+    assert_eq!(mock.login_users().await.len(), 1);
+    let requests = mock.server.received_requests().await.unwrap();
+    assert!(!requests.iter().any(|r| r.method.to_string() == "DELETE"));
+
+    // The iDRAC no longer knows the cached session, e.g. after a restart.
+    mock.serve_once("GET", "/redfish/v1", 401, json!({})).await;
+    let output = mock.cli_with_settings(cached, &args).await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(mock.login_users().await.len(), 2);
+
+    let output = mock.cli_with_settings(cached, &args).await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(mock.login_users().await.len(), 2);
+}
+
+#[tokio::test]
+async fn profiles_inherit_the_defaults_and_the_environment_overrides_them() {
+    let mock = MockIdrac::start().await;
+    let profiles = "default_profile = \"lab\"\n[hosts.lab]\nuser = \"admin\"\n[hosts.moved]\nhost = \"https://idrac.invalid\"\n";
+    let version = ["idrac", "version"];
+
+    let mut runs = vec![
+        mock.command(profiles),
+        mock.command(profiles),
+        mock.command(profiles),
+        mock.command(profiles),
+    ];
+    runs[1].env("IDRAC_USER", "operator");
+    runs[2].env("IDRAC_PROFILE", "moved").env("IDRAC_HOST", mock.uri());
+    runs[3].env("IDRAC_HOST", "https://idrac.invalid").args(["--host", &mock.uri()]);
+    for mut run in runs {
+        let output = run.args(version).output().await.unwrap();
+        assert!(output.status.success(), "{}", stderr(&output));
+    }
+    // The lab profile takes host and session_cache from the top level, the moved one the user.
+    assert_eq!(mock.login_users().await, vec!["admin", "operator", "root", "admin"]);
+
+    let output = mock.cli_with_settings(profiles, &["--profile", "moved", "idrac", "version"]).await;
+    assert_eq!(output.status.code(), Some(4), "{}", stderr(&output));
+}
+
+#[tokio::test]
+async fn session_logout_without_session() {
+    let mock = MockIdrac::start().await;
+    let output = mock.cli(&["session", "logout"]).await;
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "No active session\n");
+
+    let output = mock.cli(&["session", "logout", "-o", "json"]).await;
+    assert_eq!(serde_json::from_str::<Value>(&stdout(&output)).unwrap(), json!({ "Closed": false }));
+}
+
+#[tokio::test]
+async fn session_logout_needs_no_password() {
+    let mock = MockIdrac::start().await;
+    let _ = std::fs::remove_dir_all(mock.dir().join("idrac-cli").join("sessions"));
+    let cached = "password_command = \"exit 1\"\n[hosts.cached]\nsession_cache = true\n";
+    let output = mock.cli_with_settings(cached, &["--profile", "cached", "idrac", "version"]).await;
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let output = mock.command(cached).env_remove("IDRAC_PASSWORD").args(["--profile", "cached", "session", "logout"]).output().await.unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "Session closed\n");
+    let requests = mock.server.received_requests().await.unwrap();
+    assert_eq!(requests.iter().filter(|r| r.method.to_string() == "DELETE" && r.url.path() == SESSION).count(), 1);
+
+    let output = mock.command(cached).env_remove("IDRAC_PASSWORD").args(["session", "logout"]).output().await.unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "No active session\n");
+}
+
+#[tokio::test]
+async fn unknown_disk_exits_with_not_found() {
+    let mock = MockIdrac::start().await;
+    let output = mock.cli(&["storage", "get-storage-disk", "--name", "Disk.Bay.9"]).await;
+
+    assert_eq!(output.status.code(), Some(7));
+    assert_eq!(stderr(&output), "Error: disk 'Disk.Bay.9' was not found\n");
+}
+
+#[tokio::test]
+async fn unreachable_host_exits_with_connection_error() {
+    let mock = MockIdrac::start().await;
+    let output = mock.cli(&["--host", "http://127.0.0.1:1", "idrac", "version"]).await;
+
+    assert_eq!(output.status.code(), Some(4));
+}
+
+#[tokio::test]
+async fn missing_settings_file_exits_with_config_error() {
+    let output = tokio::process::Command::new(env!("CARGO_BIN_EXE_idrac-cli"))
+        .args(["--config", "/nonexistent/settings.toml", "idrac", "version"])
+        .output()
+        .await
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(3));
+}
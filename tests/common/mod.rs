@@ -0,0 +1,229 @@
+// Shared by several test crates, each of which only uses part of it.
+#![allow(dead_code)]
+
+use std::fs;
+use std::io::BufReader;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::process::Output;
+use std::sync::Arc;
+use serde_json::{json, Value};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::process::Command;
+use tokio_rustls::rustls::{Certificate, PrivateKey, ServerConfig};
+use tokio_rustls::TlsAcceptor;
+use wiremock::matchers::{header, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use idrac_cli::{RedfishClient, Settings};
+
+pub const TOKEN: &str = "0123456789abcdef";
+pub const SESSION: &str = "/redfish/v1/SessionService/Sessions/42";
+/// SHA-256 fingerprint of `tests/certs/idrac.pem`, as `openssl x509 -fingerprint -sha256` prints it.
+pub const FINGERPRINT: &str = "71:3C:88:49:B7:DF:C6:3D:A3:E2:D1:03:66:77:9E:0C:A2:0E:49:F2:66:31:96:FA:3B:53:A2:D9:14:D8:3A:2A";
+
+/// In-process mock iDRAC serving the JSON fixtures in `tests/fixtures`, each at its own `@odata.id`.
+pub struct MockIdrac {
+    pub server: MockServer,
+    /// Address of the TLS front of mocks started with [`start_tls`](Self::start_tls).
+    tls: Option<SocketAddr>,
+}
+
+impl MockIdrac {
+    pub async fn start() -> MockIdrac {
+        let server = MockServer::start().await;
+
+        for entry in fs::read_dir(fixtures_dir()).expect("fixtures directory exists") {
+            let file = entry.expect("fixture is readable").path();
+            let value: Value = serde_json::from_slice(&fs::read(&file).unwrap()).unwrap();
+            let id = value["@odata.id"].as_str().unwrap_or_else(|| panic!("{} has no @odata.id", file.display())).to_owned();
+            Mock::given(method("GET"))
+                .and(path(id))
+                .and(header("X-Auth-Token", TOKEN))
+                .respond_with(ResponseTemplate::new(200).set_body_json(value))
+                .mount(&server)
+                .await;
+        }
+
+        Mock::given(method("POST"))
+            .and(path("/redfish/v1/SessionService/Sessions"))
+            .respond_with(ResponseTemplate::new(201)
+                .insert_header("X-Auth-Token", TOKEN)
+                .insert_header("Location", SESSION)
+                .set_body_json(json!({ "@odata.id": SESSION, "UserName": "root" })))
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path(SESSION))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        MockIdrac { server, tls: None }
+    }
+
+    /// Like [`start`](Self::start), behind HTTPS with the certificate in `tests/certs/idrac.pem`,
+    /// issued for `localhost` by the CA in `tests/certs/ca.pem`. Neither is trusted by default.
+    pub async fn start_tls() -> MockIdrac {
+        let mut mock = MockIdrac::start().await;
+        let cert = certs_dir().join("idrac.pem");
+        let key = certs_dir().join("idrac-key.pem");
+        let certs = rustls_pemfile::certs(&mut BufReader::new(fs::File::open(cert).unwrap())).unwrap();
+        let key = rustls_pemfile::pkcs8_private_keys(&mut BufReader::new(fs::File::open(key).unwrap())).unwrap().remove(0);
+        let config = ServerConfig::builder()
+            .with_safe_defaults()
+            .with_no_client_auth()
+            .with_single_cert(certs.into_iter().map(Certificate).collect(), PrivateKey(key))
+            .unwrap();
+
+        // Terminates TLS and hands the plain connection to the mock server.
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        mock.tls = Some(listener.local_addr().unwrap());
+        let backend = *mock.server.address();
+        let acceptor = TlsAcceptor::from(Arc::new(config));
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let acceptor = acceptor.clone();
+                tokio::spawn(async move {
+                    let Ok(mut tls) = acceptor.accept(stream).await else { return };
+                    let Ok(mut plain) = TcpStream::connect(backend).await else { return };
+                    let _ = tokio::io::copy_bidirectional(&mut tls, &mut plain).await;
+                });
+            }
+        });
+        mock
+    }
+
+    pub fn uri(&self) -> String {
+        match self.tls {
+            Some(address) => format!("https://localhost:{}", address.port()),
+            None => self.server.uri(),
+        }
+    }
+
+    /// Settings pointing at the mock, without on-disk session caching.
+    pub fn settings(&self) -> Settings {
+        serde_json::from_value(json!({
+            "host": self.uri(),
+            "user": "root",
+            "password": "calvin",
+            "session_cache": false,
+        })).unwrap()
+    }
+
+    pub fn client(&self) -> RedfishClient {
+        RedfishClient::new(&self.settings()).unwrap()
+    }
+
+    /// Serves `body` with `status` at `path`, taking precedence over the fixture.
+    pub async fn serve(&self, method_name: &str, path_name: &str, status: u16, body: Value) {
+        self.respond(method_name, path_name, ResponseTemplate::new(status).set_body_json(body)).await;
+    }
+
+    /// Like [`serve`](Self::serve), for the next matching request only, e.g. to answer with a
+    /// resource as it was before the request that changes it.
+    pub async fn serve_once(&self, method_name: &str, path_name: &str, status: u16, body: Value) {
+        Mock::given(method(method_name))
+            .and(path(path_name))
+            .respond_with(ResponseTemplate::new(status).set_body_json(body))
+            .up_to_n_times(1)
+            .with_priority(1)
+            .mount(&self.server)
+            .await;
+    }
+
+    /// Like [`serve`](Self::serve), for requests with the query parameter `name=value` only, e.g.
+    /// the next page of a collection.
+    pub async fn serve_query(&self, method_name: &str, path_name: &str, (name, value): (&str, &str), status: u16, body: Value) {
+        Mock::given(method(method_name))
+            .and(path(path_name))
+            .and(query_param(name, value))
+            .respond_with(ResponseTemplate::new(status).set_body_json(body))
+            .with_priority(1)
+            .mount(&self.server)
+            .await;
+    }
+
+    /// Like [`serve`](Self::serve), for responses that need headers as well.
+    pub async fn respond(&self, method_name: &str, path_name: &str, response: ResponseTemplate) {
+        Mock::given(method(method_name))
+            .and(path(path_name))
+            .respond_with(response)
+            .with_priority(1)
+            .mount(&self.server)
+            .await;
+    }
+
+    /// Runs the CLI binary against the mock with the given arguments.
+    pub async fn cli(&self, args: &[&str]) -> Output {
+        self.cli_with_settings("", args).await
+    }
+
+    /// Directory the CLI keeps its settings, cache and data in when run against this mock.
+    pub fn dir(&self) -> PathBuf {
+        std::env::temp_dir().join(format!("idrac-cli-test-{}", self.server.address().port()))
+    }
+
+    /// Like [`cli`](Self::cli), appending `extra` to the settings file, e.g. a `[hosts.<name>]` profile.
+    pub async fn cli_with_settings(&self, extra: &str, args: &[&str]) -> Output {
+        self.command(extra).args(args).output().await.unwrap()
+    }
+
+    /// The CLI binary set up to run against the mock, with `extra` appended to the settings file
+    /// in [`settings_file`](Self::settings_file), for tests that change its environment.
+    pub fn command(&self, extra: &str) -> Command {
+        let dir = self.dir();
+        fs::create_dir_all(&dir).unwrap();
+        let config = self.settings_file();
+        fs::write(&config, format!("host = \"{}\"\nuser = \"root\"\nsession_cache = false\n{}", self.uri(), extra)).unwrap();
+
+        let mut command = Command::new(env!("CARGO_BIN_EXE_idrac-cli"));
+        command.arg("--config")
+            .arg(&config)
+            .env("IDRAC_PASSWORD", "calvin")
+            .env("XDG_CACHE_HOME", &dir)
+            .env("XDG_DATA_HOME", &dir);
+        command
+    }
+
+    pub fn settings_file(&self) -> PathBuf {
+        self.dir().join("settings.toml")
+    }
+
+    /// Passwords the CLI logged in with, in order.
+    pub async fn login_passwords(&self) -> Vec<String> {
+        self.logins("Password").await
+    }
+
+    /// Users the CLI logged in as, in order.
+    pub async fn login_users(&self) -> Vec<String> {
+        self.logins("UserName").await
+    }
+
+    async fn logins(&self, field: &str) -> Vec<String> {
+        self.server.received_requests().await.unwrap().iter()
+            .filter(|r| r.method.to_string() == "POST" && r.url.path() == "/redfish/v1/SessionService/Sessions")
+            .map(|r| r.body_json::<Value>().unwrap()[field].as_str().unwrap_or_default().to_owned())
+            .collect()
+    }
+}
+
+pub fn fixtures_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests").join("fixtures")
+}
+
+pub fn certs_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests").join("certs")
+}
+
+/// Loads a fixture so a test can serve a modified copy of it.
+pub fn fixture(name: &str) -> Value {
+    serde_json::from_slice(&fs::read(fixtures_dir().join(format!("{}.json", name))).unwrap()).unwrap()
+}
+
+pub fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+pub fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}